@@ -0,0 +1,27 @@
+use common::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// Benchmarks every phase of a day on the example from its puzzle description.
+// Save a baseline with `cargo bench -p aoc -- --save-baseline <name>` and
+// compare against it later with `cargo bench -p aoc -- --baseline <name>`.
+fn bench_day<S: Solution>(c: &mut Criterion, name: &str, example: &str) {
+    let parsed = S::parse(example).unwrap();
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("parse", |b| b.iter(|| S::parse(example).unwrap()));
+    group.bench_function("part_1", |b| b.iter(|| S::part_1(&parsed)));
+    group.bench_function("part_2", |b| b.iter(|| S::part_2(&parsed)));
+    group.finish();
+}
+
+fn examples(c: &mut Criterion) {
+    bench_day::<day7::Day7>(c, "day7", include_str!("../../day7/example.txt"));
+    bench_day::<day8::Day8>(c, "day8", include_str!("../../day8/example.txt"));
+    bench_day::<day9::Day9>(c, "day9", include_str!("../../day9/example.txt"));
+    bench_day::<day10::Day10>(c, "day10", include_str!("../../day10/example.txt"));
+    bench_day::<day12::Day12>(c, "day12", include_str!("../../day12/example.txt"));
+    bench_day::<day13::Day13>(c, "day13", include_str!("../../day13/example.txt"));
+}
+
+criterion_group!(benches, examples);
+criterion_main!(benches);
@@ -0,0 +1,328 @@
+use common::answers::{Answers, Verdict};
+use common::bench::{self, Baseline, Comparison, CountingAllocator, Measurement};
+use common::client::{Client, ClientError};
+use common::input::{self, Input, InputError, Source};
+use common::output::{self, Format};
+use common::parse::ParseError;
+use common::submit::{History, Outcome};
+use common::{solve, Answer};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+type Solver = fn(&str) -> Result<Vec<(Answer, Duration)>, ParseError>;
+type Bencher =
+    fn(&str, usize, &mut dyn FnMut(&Measurement)) -> Result<Vec<Measurement>, ParseError>;
+
+const DAYS: &[(u32, Solver, Bencher)] = &[
+    (1, solve::<day1::Day1>, bench::bench::<day1::Day1>),
+    (2, solve::<day2::Day2>, bench::bench::<day2::Day2>),
+    (3, solve::<day3::Day3>, bench::bench::<day3::Day3>),
+    (4, solve::<day4::Day4>, bench::bench::<day4::Day4>),
+    (5, solve::<day5::Day5>, bench::bench::<day5::Day5>),
+    (6, solve::<day6::Day6>, bench::bench::<day6::Day6>),
+    (7, solve::<day7::Day7>, bench::bench::<day7::Day7>),
+    (8, solve::<day8::Day8>, bench::bench::<day8::Day8>),
+    (9, solve::<day9::Day9>, bench::bench::<day9::Day9>),
+    (10, solve::<day10::Day10>, bench::bench::<day10::Day10>),
+    (11, solve::<day11::Day11>, bench::bench::<day11::Day11>),
+    (12, solve::<day12::Day12>, bench::bench::<day12::Day12>),
+    (13, solve::<day13::Day13>, bench::bench::<day13::Day13>),
+    (14, solve::<day14::Day14>, bench::bench::<day14::Day14>),
+];
+
+fn crate_dir(day: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{}", day))
+}
+
+fn load(source: Source) -> Result<Input, InputError> {
+    let contents = input::load(&source)?;
+    Ok(Input { source, contents })
+}
+
+fn print_answer(day: u32, part: u32, answer: &str, verdict: Verdict) {
+    // Some answers (e.g. day 10's message) are drawings spanning several lines
+    if answer.contains('\n') {
+        println!("day {} part {} ({}):\n{}", day, part, verdict, answer);
+    } else {
+        println!("day {} part {}: {} ({})", day, part, answer, verdict);
+    }
+}
+
+fn run(
+    day: u32,
+    solve: Solver,
+    source: Source,
+    answers: &mut Answers,
+    record: bool,
+    format: Format,
+) -> Result<(), InputError> {
+    let input = load(source)?;
+    let solved = solve(&input.contents).unwrap_or_else(|e| input.exit_with(&e));
+
+    for (part, (answer, elapsed)) in (1..).zip(solved) {
+        match format {
+            Format::Text => {
+                let verdict = answers.check(day, part, &input.contents, &answer.value);
+                print_answer(day, part, &answer.value, verdict);
+            }
+            Format::Json => println!("{}", output::to_json(day, part, &answer, elapsed)),
+        }
+
+        if record {
+            answers.record(day, part, &input.contents, &answer.value);
+        }
+    }
+    Ok(())
+}
+
+fn fetch(client: &Client, day: u32, force: bool) -> Result<(), ClientError> {
+    match client.cached_input(day) {
+        Some(path) if !force => println!("day {}: already cached at {}", day, path.display()),
+        _ => {
+            let path = client.download_input(day)?;
+            println!("day {}: downloaded to {}", day, path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Submits the given answer, or the one the solver comes up with for the
+/// default input. Answers that the history already knows to be wrong are
+/// never sent.
+fn submit(
+    client: &Client,
+    day: &(u32, Solver, Bencher),
+    part: u32,
+    answer: Option<String>,
+) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let &(day, solve, _) = day;
+    let input = load(Source::from_arg(None, &crate_dir(day)))?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let mut solved = solve(&input.contents).unwrap_or_else(|e| input.exit_with(&e));
+            solved.swap_remove(part as usize - 1).0.value
+        }
+    };
+
+    let mut history = History::load(&client.cache_dir)?;
+    if let Err(refusal) = history.attempts(day, part).check(&answer) {
+        return Err(format!("not submitting {}: {}", answer, refusal).into());
+    }
+
+    println!("day {} part {}: submitting {}", day, part, answer);
+    let outcome = client.submit_answer(day, part, &answer)?;
+    history.attempts(day, part).record(&answer, &outcome);
+    history.save()?;
+
+    // The server knows best, so its answer is also the one to check against.
+    if outcome == Outcome::Correct {
+        let mut answers = Answers::load()?;
+        answers.record(day, part, &input.contents, &answer);
+        answers.save()?;
+    }
+    Ok(outcome)
+}
+
+/// Returns the number of phases that got slower than the baseline allows.
+fn run_bench(
+    day: u32,
+    bench: Bencher,
+    source: Source,
+    runs: usize,
+    baseline: &mut Baseline,
+    save_baseline: bool,
+) -> Result<usize, InputError> {
+    let input = load(source)?;
+    let mut regressions = 0;
+
+    let measurements = bench(&input.contents, runs, &mut |measurement| {
+        let comparison = baseline.compare(day, measurement);
+        if let Comparison::Regression(_) = comparison {
+            regressions += 1;
+        }
+        println!(
+            "day {:>2} {:<6}  {}  ({})",
+            day, measurement.phase, measurement, comparison
+        );
+    })
+    .unwrap_or_else(|e| input.exit_with(&e));
+
+    if save_baseline {
+        for measurement in &measurements {
+            baseline.record(day, measurement);
+        }
+    }
+    Ok(regressions)
+}
+
+fn usage() -> ! {
+    eprintln!("usage: aoc run <day> [<input>|-] [--record] [--format <text|json>]");
+    eprintln!("       aoc run all [--record] [--format <text|json>]");
+    eprintln!("       aoc bench <day> [<input>|-] [--runs <n>] [--save-baseline]");
+    eprintln!("       aoc bench all [--runs <n>] [--save-baseline]");
+    eprintln!("       aoc fetch <day>|all [--force]");
+    eprintln!("       aoc submit <day> <1|2> [<answer>]");
+    process::exit(1);
+}
+
+/// Removes `flag` from `args` and returns whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+/// Removes `option` and the value following it from `args`.
+fn take_option(args: &mut Vec<String>, option: &str) -> Option<String> {
+    let i = args.iter().position(|arg| arg == option)?;
+    if i + 1 >= args.len() {
+        usage();
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Some(value)
+}
+
+fn find_day(arg: &str) -> &'static (u32, Solver, Bencher) {
+    let day = arg.parse::<u32>().unwrap_or_else(|_| usage());
+    DAYS.iter().find(|(d, _, _)| *d == day).unwrap_or_else(|| {
+        eprintln!("no solution for day {}", day);
+        process::exit(1);
+    })
+}
+
+fn main() -> Result<(), std::io::Error> {
+    let mut args: Vec<String> = env::args().collect();
+    let record = take_flag(&mut args, "--record");
+    let save_baseline = take_flag(&mut args, "--save-baseline");
+    let force = take_flag(&mut args, "--force");
+    let format = Format::from_args();
+    take_option(&mut args, "--format");
+    let runs = take_option(&mut args, "--runs")
+        .map(|runs| runs.parse::<usize>().unwrap_or_else(|_| usage()))
+        .unwrap_or(1);
+
+    if args.len() < 3 {
+        usage();
+    }
+
+    if args[1] == "fetch" {
+        let days: Vec<u32> = if args[2] == "all" {
+            DAYS.iter().map(|&(day, _, _)| day).collect()
+        } else {
+            vec![find_day(&args[2]).0]
+        };
+
+        let result = Client::from_env()
+            .and_then(|client| days.iter().try_for_each(|&day| fetch(&client, day, force)));
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args[1] == "submit" {
+        let day = find_day(&args[2]);
+        let part = match args.get(3).map(String::as_str) {
+            Some("1") => 1,
+            Some("2") => 2,
+            _ => usage(),
+        };
+
+        let result = Client::from_env()
+            .map_err(|e| e.into())
+            .and_then(|client| submit(&client, day, part, args.get(4).cloned()));
+        match result {
+            Ok(outcome) => {
+                println!("day {} part {}: {}", day.0, part, outcome);
+                if outcome != Outcome::Correct {
+                    process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // Every day outside of `all` may be given its own input. `all` skips the
+    // days that neither have been fetched nor have an `input.txt`.
+    let days: Vec<(&(u32, Solver, Bencher), Source)> = if args[2] == "all" {
+        DAYS.iter()
+            .filter_map(|day| match Source::from_arg(None, &crate_dir(day.0)) {
+                Source::File(ref path) if !path.exists() => {
+                    eprintln!(
+                        "day {}: no input, skipping (run `aoc fetch {}`)",
+                        day.0, day.0
+                    );
+                    None
+                }
+                source => Some((day, source)),
+            })
+            .collect()
+    } else {
+        let day = find_day(&args[2]);
+        let arg = args.get(3).map(String::as_str);
+        let source = Source::from_arg(arg, &crate_dir(day.0));
+        if let (None, Source::File(path)) = (arg, &source) {
+            if !path.exists() {
+                eprintln!("{}", InputError::NotFetched(source, day.0));
+                process::exit(1);
+            }
+        }
+        vec![(day, source)]
+    };
+
+    match args[1].as_str() {
+        "run" => {
+            let mut answers = Answers::load()?;
+            let result = days.into_iter().try_for_each(|(&(day, solve, _), source)| {
+                run(day, solve, source, &mut answers, record, format)
+            });
+
+            if let Err(e) = result {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+
+            if record {
+                answers.save()?;
+            }
+        }
+        "bench" => {
+            let mut baseline = Baseline::load()?;
+            let result = days
+                .into_iter()
+                .try_fold(0, |regressions, (&(day, _, bench), source)| {
+                    run_bench(day, bench, source, runs, &mut baseline, save_baseline)
+                        .map(|r| regressions + r)
+                });
+
+            let regressions = result.unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+
+            if save_baseline {
+                baseline.save()?;
+            } else if regressions > 0 {
+                eprintln!("{} phase(s) regressed against the baseline", regressions);
+                process::exit(1);
+            }
+        }
+        _ => usage(),
+    }
+
+    Ok(())
+}
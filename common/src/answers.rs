@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// day -> input hash -> part -> answer, e.g. `[day7.9f86d081884c7d65] part_1 = "..."`
+type Registry = BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>;
+
+/// Known correct answers, keyed by day, the hash of the input and the part.
+/// This way the binaries can tell whether they still produce the right answer
+/// for a given input without hard-coding it.
+pub struct Answers {
+    path: PathBuf,
+    registry: Registry,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// FNV-1a over the input without trailing whitespace. It only has to be
+/// stable across runs and machines, which `DefaultHasher` doesn't promise.
+pub fn input_hash(input: &str) -> String {
+    let hash = input
+        .trim_end()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash: u64, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+fn default_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("answers.toml")
+}
+
+impl Answers {
+    /// Loads the `answers.toml` at the root of the workspace. A missing file
+    /// is the same as an empty one.
+    pub fn load() -> io::Result<Answers> {
+        let path = default_path();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let registry = toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+
+        Ok(Answers { path, registry })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents = toml::to_string(&self.registry)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&self.path, contents)
+    }
+
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<&String> {
+        self.registry
+            .get(&format!("day{}", day))?
+            .get(&input_hash(input))?
+            .get(&format!("part_{}", part))
+    }
+
+    pub fn check(&self, day: u32, part: u32, input: &str, answer: &str) -> Verdict {
+        match self.get(day, part, input) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub fn record(&mut self, day: u32, part: u32, input: &str, answer: &str) {
+        self.registry
+            .entry(format!("day{}", day))
+            .or_default()
+            .entry(input_hash(input))
+            .or_default()
+            .insert(format!("part_{}", part), answer.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_answers() -> Answers {
+        Answers {
+            path: PathBuf::new(),
+            registry: Registry::new(),
+        }
+    }
+
+    #[test]
+    fn hashing_input_ignores_trailing_newlines() {
+        assert_eq!(input_hash("5177"), input_hash("5177\n"));
+        assert_ne!(input_hash("5177"), input_hash("5178"));
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn checking_answers() {
+        let mut answers = empty_answers();
+        assert_eq!(answers.check(7, 1, "input", "CABDFE"), Verdict::Unknown);
+
+        answers.record(7, 1, "input", "CABDFE");
+        assert_eq!(answers.check(7, 1, "input", "CABDFE"), Verdict::Pass);
+        assert_eq!(
+            answers.check(7, 1, "input", "CABFDE"),
+            Verdict::Fail(String::from("CABDFE"))
+        );
+        assert_eq!(answers.check(7, 2, "input", "CABDFE"), Verdict::Unknown);
+        assert_eq!(
+            answers.check(7, 1, "other input", "CABDFE"),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn registry_roundtrip() {
+        let mut answers = empty_answers();
+        answers.record(7, 1, "input", "CABDFE");
+        answers.record(7, 2, "input", "15");
+
+        let contents = toml::to_string(&answers.registry).unwrap();
+        let registry: Registry = toml::from_str(&contents).unwrap();
+        assert_eq!(registry, answers.registry);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::parse::ParseError;
+use crate::Solution;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, but counting every allocation. Binaries that want
+/// allocation numbers in their measurements install it with
+/// `#[global_allocator]`; without it they're always 0.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // A growing `Vec` reallocates, which is as much of an allocation as
+        // the first one.
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// How long one phase (parsing or one of the parts) took and how much it
+/// allocated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub phase: &'static str,
+    pub elapsed: Duration,
+    pub allocations: usize,
+    pub bytes: usize,
+}
+
+impl Measurement {
+    /// Keeps the faster of both runs. Allocations don't change between runs.
+    fn fastest(self, other: Measurement) -> Measurement {
+        if other.elapsed < self.elapsed {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>12?}  {:>9} allocs  {:>10}",
+            self.elapsed,
+            self.allocations,
+            format_bytes(self.bytes)
+        )
+    }
+}
+
+pub fn measure<T, F: FnOnce() -> T>(phase: &'static str, f: F) -> (T, Measurement) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    let result = f();
+
+    let measurement = Measurement {
+        phase,
+        elapsed: start.elapsed(),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    };
+    (result, measurement)
+}
+
+fn measure_fastest<T, F: FnMut() -> T>(
+    phase: &'static str,
+    runs: usize,
+    mut f: F,
+) -> (T, Measurement) {
+    let (mut result, mut fastest) = measure(phase, &mut f);
+    for _ in 1..runs {
+        let (next, measurement) = measure(phase, &mut f);
+        result = next;
+        fastest = fastest.fastest(measurement);
+    }
+    (result, fastest)
+}
+
+/// Runs parsing and both parts `runs` times each and keeps the fastest run.
+/// `report` is called as soon as a phase is done, so slow days show progress.
+pub fn bench<S: Solution>(
+    input: &str,
+    runs: usize,
+    report: &mut dyn FnMut(&Measurement),
+) -> Result<Vec<Measurement>, ParseError> {
+    let (parsed, parse) = measure_fastest("parse", runs, || S::parse(input));
+    let parsed = parsed?;
+    report(&parse);
+
+    let (_, part_1) = measure_fastest("part_1", runs, || S::part_1(&parsed));
+    report(&part_1);
+
+    let (_, part_2) = measure_fastest("part_2", runs, || S::part_2(&parsed));
+    report(&part_2);
+
+    Ok(vec![parse, part_1, part_2])
+}
+
+/// A phase counts as a regression once it's this much slower than the baseline.
+pub const REGRESSION_THRESHOLD: f64 = 1.25;
+
+// Phases faster than this are mostly noise and never count as a regression.
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+#[derive(Debug, PartialEq)]
+pub enum Comparison {
+    Faster(f64),
+    Slower(f64),
+    Regression(f64),
+    Unknown,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Comparison::Faster(ratio) => write!(f, "-{:.1}% vs baseline", (1.0 - ratio) * 100.0),
+            Comparison::Slower(ratio) => write!(f, "+{:.1}% vs baseline", (ratio - 1.0) * 100.0),
+            Comparison::Regression(ratio) => {
+                write!(f, "REGRESSION, +{:.1}% vs baseline", (ratio - 1.0) * 100.0)
+            }
+            Comparison::Unknown => write!(f, "no baseline"),
+        }
+    }
+}
+
+// day -> phase -> nanoseconds, e.g. `[day9] part_2 = 3_201_000_000`
+type Registry = BTreeMap<String, BTreeMap<String, u64>>;
+
+/// Timings of an earlier `aoc bench --save-baseline` to compare against.
+/// They only make sense on the machine they were taken on, which is why
+/// `bench_baseline.toml` isn't checked in.
+pub struct Baseline {
+    path: PathBuf,
+    registry: Registry,
+}
+
+fn default_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("bench_baseline.toml")
+}
+
+impl Baseline {
+    pub fn load() -> io::Result<Baseline> {
+        let path = default_path();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let registry = toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+
+        Ok(Baseline { path, registry })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents = toml::to_string(&self.registry)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&self.path, contents)
+    }
+
+    pub fn compare(&self, day: u32, measurement: &Measurement) -> Comparison {
+        let nanos = self
+            .registry
+            .get(&format!("day{}", day))
+            .and_then(|phases| phases.get(measurement.phase));
+
+        let baseline = match nanos {
+            Some(&nanos) if nanos > 0 => Duration::from_nanos(nanos),
+            _ => return Comparison::Unknown,
+        };
+
+        let ratio = measurement.elapsed.as_nanos() as f64 / baseline.as_nanos() as f64;
+        if ratio <= 1.0 {
+            Comparison::Faster(ratio)
+        } else if ratio > REGRESSION_THRESHOLD && measurement.elapsed - baseline > NOISE_FLOOR {
+            Comparison::Regression(ratio)
+        } else {
+            Comparison::Slower(ratio)
+        }
+    }
+
+    pub fn record(&mut self, day: u32, measurement: &Measurement) {
+        self.registry
+            .entry(format!("day{}", day))
+            .or_default()
+            .insert(
+                measurement.phase.to_string(),
+                measurement.elapsed.as_nanos() as u64,
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(phase: &'static str, millis: u64) -> Measurement {
+        Measurement {
+            phase,
+            elapsed: Duration::from_millis(millis),
+            allocations: 0,
+            bytes: 0,
+        }
+    }
+
+    #[test]
+    fn comparing_against_the_baseline() {
+        let mut baseline = Baseline {
+            path: PathBuf::new(),
+            registry: Registry::new(),
+        };
+        assert_eq!(
+            baseline.compare(9, &measurement("part_2", 100)),
+            Comparison::Unknown
+        );
+
+        baseline.record(9, &measurement("part_2", 100));
+        assert_eq!(
+            baseline.compare(9, &measurement("part_2", 50)),
+            Comparison::Faster(0.5)
+        );
+        assert_eq!(
+            baseline.compare(9, &measurement("part_2", 110)),
+            Comparison::Slower(1.1)
+        );
+        assert_eq!(
+            baseline.compare(9, &measurement("part_2", 200)),
+            Comparison::Regression(2.0)
+        );
+        assert_eq!(
+            baseline.compare(9, &measurement("part_1", 100)),
+            Comparison::Unknown
+        );
+    }
+
+    #[test]
+    fn tiny_phases_are_never_regressions() {
+        let mut baseline = Baseline {
+            path: PathBuf::new(),
+            registry: Registry::new(),
+        };
+        baseline
+            .registry
+            .entry(String::from("day8"))
+            .or_default()
+            .insert(String::from("parse"), 100_000);
+
+        let measurement = Measurement {
+            phase: "parse",
+            elapsed: Duration::from_micros(300),
+            allocations: 0,
+            bytes: 0,
+        };
+        assert_eq!(baseline.compare(8, &measurement), Comparison::Slower(3.0));
+    }
+
+    #[test]
+    fn formatting_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+}
@@ -0,0 +1,32 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The year all of the puzzles in this repository are from.
+pub const YEAR: u32 = 2018;
+
+/// Where `aoc fetch` keeps downloaded inputs: `$AOC_CACHE_DIR` if it's set,
+/// otherwise `aoc` in the user's cache directory.
+pub fn dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("aoc"));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("aoc"))
+}
+
+pub fn input_path(cache_dir: &Path, day: u32) -> PathBuf {
+    cache_dir
+        .join(YEAR.to_string())
+        .join(format!("day{}.txt", day))
+}
+
+/// Where the session cookie is read from if `$AOC_SESSION` isn't set.
+pub fn session_path() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("aoc").join("session"))
+}
@@ -0,0 +1,396 @@
+use crate::cache;
+use crate::submit::{self, Outcome};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/mrnugget/advent_of_code";
+
+/// The least time between two requests, so that fetching every day in a row
+/// doesn't hammer the server.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// The longest `Retry-After` that's waited for before trying a rate limited
+/// request once more. Longer ones are left to the user.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    RateLimited { retry_after: Option<Duration> },
+    Status { code: u16, message: String },
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session cookie, set AOC_SESSION or put it into {}",
+                cache::session_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| String::from("~/.config/aoc/session"))
+            ),
+            ClientError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "rate limited, try again in {}s", retry_after.as_secs()),
+            ClientError::RateLimited { retry_after: None } => {
+                write!(f, "rate limited, try again later")
+            }
+            ClientError::Status { code, message } => write!(f, "HTTP {}: {}", code, message),
+            ClientError::Transport(e) => write!(f, "request failed: {}", e),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> ClientError {
+        ClientError::Io(e)
+    }
+}
+
+/// Talks to adventofcode.com, or whatever `base_url` points to.
+pub struct Client {
+    pub base_url: String,
+    pub session: String,
+    pub cache_dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+impl Client {
+    /// Reads the base URL from `$AOC_BASE_URL` and the session cookie from
+    /// `$AOC_SESSION` or the session file, falling back to the defaults.
+    pub fn from_env() -> Result<Client, ClientError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => cache::session_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .ok_or(ClientError::NoSession)?,
+        };
+        let cache_dir = cache::dir().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "no cache directory, set AOC_CACHE_DIR",
+            )
+        })?;
+
+        Ok(Client {
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| String::from(DEFAULT_BASE_URL)),
+            session: session.trim().to_string(),
+            cache_dir,
+            min_interval: MIN_INTERVAL,
+        })
+    }
+
+    /// The input of `day`, if it's been downloaded already.
+    pub fn cached_input(&self, day: u32) -> Option<PathBuf> {
+        let path = cache::input_path(&self.cache_dir, day);
+        if path.exists() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Downloads the input of `day` into the cache and returns its path.
+    pub fn download_input(&self, day: u32) -> Result<PathBuf, ClientError> {
+        let input = self.get(&format!("/{}/day/{}/input", cache::YEAR, day))?;
+
+        let path = cache::input_path(&self.cache_dir, day);
+        fs::create_dir_all(path.parent().unwrap())?;
+        // Written in one go, so that a failed download never leaves half an
+        // input behind that later runs would pick up.
+        let partial = path.with_extension("txt.part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+
+    /// Submits `answer` for `part` of `day` and tells what the server made
+    /// of it.
+    pub fn submit_answer(&self, day: u32, part: u32, answer: &str) -> Result<Outcome, ClientError> {
+        let body = self.post(
+            &format!("/{}/day/{}/answer", cache::YEAR, day),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+        Ok(submit::parse_outcome(&body))
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        self.send(|| read_response(self.request("GET", path).call()))
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        self.send(|| read_response(self.request("POST", path).send_form(form)))
+    }
+
+    /// Sends a request, and once more if the server rate limits it but says
+    /// to retry within `MAX_RETRY_AFTER`.
+    fn send<F>(&self, request: F) -> Result<String, ClientError>
+    where
+        F: Fn() -> Result<String, ClientError>,
+    {
+        self.throttle()?;
+        match request() {
+            Err(ClientError::RateLimited {
+                retry_after: Some(retry_after),
+            }) if retry_after <= MAX_RETRY_AFTER => {
+                thread::sleep(retry_after);
+                self.throttle()?;
+                request()
+            }
+            response => response,
+        }
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        ureq::request(method, &format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request.
+    fn throttle(&self) -> io::Result<()> {
+        // The marker holds the time of the last request. Its modification
+        // time would do as well, if file systems didn't round it.
+        let marker = self.cache_dir.join("last_request");
+        let last = fs::read_to_string(&marker)
+            .ok()
+            .and_then(|millis| millis.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+        if let Some(last) = last {
+            // A request that seems to come from the future was just now.
+            let since_last = SystemTime::now().duration_since(last).unwrap_or_default();
+            if since_last < self.min_interval {
+                thread::sleep(self.min_interval - since_last);
+            }
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(marker, now.as_millis().to_string())
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(code, response)) => Err(status_error(code, response)),
+        Err(e) => Err(ClientError::Transport(e.to_string())),
+    }
+}
+
+fn status_error(code: u16, response: ureq::Response) -> ClientError {
+    if code == 429 {
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        return ClientError::RateLimited { retry_after };
+    }
+
+    // The server explains most errors in the first line of the body, like
+    // "Please log in to get your puzzle input."
+    let body = response.into_string().unwrap_or_default();
+    let message = body
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no explanation")
+        .to_string();
+    ClientError::Status { code, message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use tiny_http::{Header, Response, Server};
+
+    struct Request {
+        method: String,
+        url: String,
+        cookie: Option<String>,
+        body: String,
+    }
+
+    /// Starts a server that answers the given responses in order and
+    /// reports every request it gets.
+    fn stub_server(
+        responses: Vec<(u16, &'static str, Option<&'static str>)>,
+    ) -> (String, mpsc::Receiver<Request>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, response_body, retry_after) in responses {
+                let mut request = server.recv().unwrap();
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                sender
+                    .send(Request {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie,
+                        body,
+                    })
+                    .unwrap();
+
+                let mut response = Response::from_string(response_body).with_status_code(status);
+                if let Some(retry_after) = retry_after {
+                    response.add_header(Header::from_bytes("Retry-After", retry_after).unwrap());
+                }
+                request.respond(response).unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    fn client(base_url: String, name: &str) -> Client {
+        let cache_dir = env::temp_dir().join(format!("aoc-client-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&cache_dir);
+
+        Client {
+            base_url,
+            session: String::from("53cr3t"),
+            cache_dir,
+            min_interval: Duration::from_secs(0),
+        }
+    }
+
+    #[test]
+    fn downloading_inputs_into_the_cache() {
+        let (base_url, requests) = stub_server(vec![(200, "+1\n-2\n+3\n+1\n", None)]);
+        let client = client(base_url, "download");
+        assert_eq!(client.cached_input(1), None);
+
+        let path = client.download_input(1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "+1\n-2\n+3\n+1\n");
+        assert_eq!(client.cached_input(1), Some(path));
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.url, "/2018/day/1/input");
+        assert_eq!(request.cookie.as_deref(), Some("session=53cr3t"));
+
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn reporting_rate_limits() {
+        let (base_url, _requests) = stub_server(vec![(429, "", Some("120")), (429, "", None)]);
+        let client = client(base_url, "rate-limit");
+
+        match client.download_input(3) {
+            Err(ClientError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(120)))
+            }
+            other => panic!("expected a rate limit, got {:?}", other),
+        }
+        match client.download_input(3) {
+            Err(ClientError::RateLimited { retry_after }) => assert_eq!(retry_after, None),
+            other => panic!("expected a rate limit, got {:?}", other),
+        }
+        assert_eq!(client.cached_input(3), None);
+
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn retrying_after_short_rate_limits() {
+        let (base_url, requests) = stub_server(vec![(429, "", Some("1")), (200, "+7\n", None)]);
+        let client = client(base_url, "retry");
+
+        let start = SystemTime::now();
+        let path = client.download_input(6).unwrap();
+        assert!(start.elapsed().unwrap() >= Duration::from_secs(1));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "+7\n");
+        assert_eq!(requests.iter().count(), 2);
+
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn reporting_other_responses() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        let (base_url, _requests) = stub_server(vec![(400, body, None), (500, "", None)]);
+        let client = client(base_url, "status");
+
+        let error = client.download_input(4).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "HTTP 400: Puzzle inputs differ by user.  Please log in to get your puzzle input."
+        );
+        let error = client.download_input(4).unwrap_err();
+        assert_eq!(error.to_string(), "HTTP 500: no explanation");
+        assert_eq!(client.cached_input(4), None);
+
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn throttling_requests() {
+        let (base_url, _requests) = stub_server(vec![(200, "1", None), (200, "2", None)]);
+        let mut client = client(base_url, "throttle");
+        client.min_interval = Duration::from_millis(300);
+
+        let start = SystemTime::now();
+        client.download_input(5).unwrap();
+        client.download_input(5).unwrap();
+        assert!(start.elapsed().unwrap() >= Duration::from_millis(300));
+
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn submitting_answers() {
+        let (base_url, requests) = stub_server(vec![
+            (
+                200,
+                "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+                None,
+            ),
+            (
+                200,
+                "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article></main>",
+                None,
+            ),
+        ]);
+        let client = client(base_url, "submit");
+
+        assert_eq!(client.submit_answer(9, 2, "1337").unwrap(), Outcome::TooLow);
+        assert_eq!(
+            client.submit_answer(9, 2, "3314195047").unwrap(),
+            Outcome::Correct
+        );
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "/2018/day/9/answer");
+        assert_eq!(request.cookie.as_deref(), Some("session=53cr3t"));
+        assert_eq!(request.body, "level=2&answer=1337");
+        assert_eq!(requests.recv().unwrap().body, "level=2&answer=3314195047");
+
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,352 @@
+use crate::cache;
+use crate::parse::ParseError;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// `-` means stdin. No argument at all means the input `aoc fetch`
+    /// downloaded for the crate's day, or `input.txt` in `crate_dir` if there
+    /// is none.
+    pub fn from_arg(arg: Option<&str>, crate_dir: &Path) -> Source {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+            None => Source::File(default_path(crate_dir, cache::dir())),
+        }
+    }
+}
+
+// Every day lives in a crate called `day<n>`.
+fn crate_day(crate_dir: &Path) -> Option<u32> {
+    crate_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("day"))
+        .and_then(|day| day.parse::<u32>().ok())
+}
+
+fn default_path(crate_dir: &Path, cache_dir: Option<PathBuf>) -> PathBuf {
+    match (crate_day(crate_dir), cache_dir) {
+        (Some(day), Some(cache_dir)) if cache::input_path(&cache_dir, day).exists() => {
+            cache::input_path(&cache_dir, day)
+        }
+        _ => crate_dir.join("input.txt"),
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(Source, io::Error),
+    /// There's neither a downloaded input for the day nor an `input.txt`.
+    NotFetched(Source, u32),
+    InvalidUtf8 {
+        source: Source,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io(source, e) => write!(f, "{}: {}", source, e),
+            InputError::NotFetched(source, day) => write!(
+                f,
+                "{}: no input for day {}, run `aoc fetch {}` to download it",
+                source, day, day
+            ),
+            InputError::InvalidUtf8 {
+                source,
+                line,
+                column,
+            } => write!(
+                f,
+                "{}:{}:{}: input is not valid UTF-8",
+                source, line, column
+            ),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// The puzzle input together with where it came from, so that errors can
+/// point into it.
+pub struct Input {
+    pub source: Source,
+    pub contents: String,
+}
+
+impl Input {
+    /// Prints `error` as a diagnostic pointing into this input and exits.
+    pub fn exit_with(&self, error: &ParseError) -> ! {
+        eprintln!("{}", error.diagnostic(&self.source));
+        process::exit(1);
+    }
+}
+
+fn read_bytes(source: &Source) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match source {
+        Source::File(path) => bytes = fs::read(path)?,
+        Source::Stdin => {
+            io::stdin().read_to_end(&mut bytes)?;
+        }
+    };
+    Ok(bytes)
+}
+
+fn decode(source: &Source, bytes: Vec<u8>) -> Result<String, InputError> {
+    let mut contents = String::from_utf8(bytes).map_err(|e| {
+        let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+        let line_start = valid.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+
+        InputError::InvalidUtf8 {
+            line: valid.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: valid.len() - line_start + 1,
+            source: source.clone(),
+        }
+    })?;
+
+    // Editors and `echo` disagree on whether there's a final newline, so the
+    // days never get to see any.
+    let trimmed_len = contents.trim_end_matches(&['\n', '\r'][..]).len();
+    contents.truncate(trimmed_len);
+    Ok(contents)
+}
+
+pub fn load(source: &Source) -> Result<String, InputError> {
+    match read_bytes(source) {
+        Ok(bytes) => decode(source, bytes),
+        Err(e) => Err(InputError::Io(source.clone(), e)),
+    }
+}
+
+// Options every binary has that are followed by a value.
+const OPTIONS_WITH_VALUE: &[&str] = &["--format"];
+
+fn option_value<I: Iterator<Item = String>>(mut args: I, name: &str) -> Option<String> {
+    args.find(|arg| arg == name)?;
+    args.next()
+}
+
+/// The value following `--name` on the command line, if it's there.
+pub fn option(name: &str) -> Option<String> {
+    option_value(env::args().skip(1), name)
+}
+
+// `options` are the binary's own options that are followed by a value,
+// which mustn't be mistaken for an argument.
+fn positional_args<I: Iterator<Item = String>>(mut args: I, options: &[&str]) -> Vec<String> {
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        if OPTIONS_WITH_VALUE.contains(&arg.as_str()) || options.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with("--") {
+            positional.push(arg);
+        }
+    }
+    positional
+}
+
+/// The arguments that aren't `--flags` or the values of options, like
+/// subcommands and the name of the input. `options` are the binary's own
+/// options that are followed by a value.
+pub fn positional(options: &[&str]) -> Vec<String> {
+    positional_args(env::args().skip(1), options)
+}
+
+/// Loads the input named by the first argument that isn't a `--flag`,
+/// falling back to the `input.txt` of the binary's crate. `options` are the
+/// binary's own options that are followed by a value.
+pub fn from_args(crate_dir: &str, options: &[&str]) -> Result<Input, InputError> {
+    from_args_after(crate_dir, options, 0)
+}
+
+/// Like `from_args`, for binaries with subcommands: the input is named by
+/// the argument after the first `skip` ones that aren't `--flags`, which
+/// are the subcommand and its arguments.
+pub fn from_args_after(
+    crate_dir: &str,
+    options: &[&str],
+    skip: usize,
+) -> Result<Input, InputError> {
+    let arg = positional_args(env::args().skip(1), options)
+        .into_iter()
+        .nth(skip);
+
+    read_input(arg.as_deref(), Path::new(crate_dir))
+}
+
+// Like `load`, but telling to fetch the day's input if there's none at the
+// default path.
+fn read_input(arg: Option<&str>, crate_dir: &Path) -> Result<Input, InputError> {
+    let source = Source::from_arg(arg, crate_dir);
+    let contents = match (load(&source), crate_day(crate_dir)) {
+        (Err(InputError::Io(source, e)), Some(day))
+            if arg.is_none() && e.kind() == io::ErrorKind::NotFound =>
+        {
+            return Err(InputError::NotFetched(source, day))
+        }
+        (contents, _) => contents?,
+    };
+    Ok(Input { source, contents })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> Source {
+        Source::File(PathBuf::from("input.txt"))
+    }
+
+    #[test]
+    fn choosing_the_source() {
+        let crate_dir = Path::new("day7");
+
+        assert_eq!(Source::from_arg(Some("-"), crate_dir), Source::Stdin);
+        assert_eq!(
+            Source::from_arg(Some("other.txt"), crate_dir),
+            Source::File(PathBuf::from("other.txt"))
+        );
+    }
+
+    #[test]
+    fn preferring_cached_inputs() {
+        let cache_dir = env::temp_dir().join(format!("aoc-input-{}", process::id()));
+        let crate_dir = Path::new("day7");
+
+        assert_eq!(
+            default_path(crate_dir, Some(cache_dir.clone())),
+            PathBuf::from("day7/input.txt")
+        );
+        assert_eq!(
+            default_path(crate_dir, None),
+            PathBuf::from("day7/input.txt")
+        );
+
+        let cached = cache::input_path(&cache_dir, 7);
+        fs::create_dir_all(cached.parent().unwrap()).unwrap();
+        fs::write(&cached, "Step C must be finished before step A can begin.").unwrap();
+        assert_eq!(default_path(crate_dir, Some(cache_dir.clone())), cached);
+        assert_eq!(
+            default_path(Path::new("day8"), Some(cache_dir.clone())),
+            PathBuf::from("day8/input.txt")
+        );
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn skipping_options() {
+        let args = |args: &[&str]| {
+            positional_args(args.iter().map(|arg| arg.to_string()), &["--cluster"])
+                .into_iter()
+                .next()
+        };
+
+        assert_eq!(
+            args(&["--record", "other.txt"]),
+            Some(String::from("other.txt"))
+        );
+        assert_eq!(args(&["--format", "json"]), None);
+        assert_eq!(args(&["--format", "json", "-"]), Some(String::from("-")));
+        assert_eq!(
+            args(&["--cluster", "2", "ids.txt"]),
+            Some(String::from("ids.txt"))
+        );
+        assert_eq!(
+            positional_args(
+                ["guard", "--window", "23:00-01:00", "10", "-"]
+                    .iter()
+                    .map(|arg| arg.to_string()),
+                &["--window"]
+            ),
+            vec!["guard", "10", "-"]
+        );
+        // Other binaries' options don't take a value.
+        assert_eq!(
+            positional_args(
+                ["--guards", "input.txt"].iter().map(|arg| arg.to_string()),
+                &["--cluster"]
+            ),
+            vec!["input.txt"]
+        );
+    }
+
+    #[test]
+    fn reading_option_values() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let value = option_value(args(&["-", "--cluster", "2"]).into_iter(), "--cluster");
+        assert_eq!(value, Some(String::from("2")));
+        assert_eq!(
+            option_value(args(&["--cluster"]).into_iter(), "--cluster"),
+            None
+        );
+        assert_eq!(option_value(args(&["-"]).into_iter(), "--cluster"), None);
+    }
+
+    #[test]
+    fn trimming_trailing_newlines() {
+        let input = b"#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\r\n\n".to_vec();
+        assert_eq!(
+            decode(&source(), input).unwrap(),
+            "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4"
+        );
+
+        let input = b"  \\------/   \n".to_vec();
+        assert_eq!(decode(&source(), input).unwrap(), "  \\------/   ");
+    }
+
+    #[test]
+    fn reporting_invalid_utf8() {
+        let input = b"2 3 0 3\n10 11 \xff 12\n".to_vec();
+        let error = decode(&source(), input).unwrap_err();
+        assert_eq!(error.to_string(), "input.txt:2:7: input is not valid UTF-8");
+    }
+
+    #[test]
+    fn reporting_missing_files() {
+        let missing = Source::File(PathBuf::from("does/not/exist.txt"));
+        let error = load(&missing).unwrap_err();
+        assert!(error.to_string().starts_with("does/not/exist.txt: "));
+    }
+
+    #[test]
+    fn telling_to_fetch_missing_inputs() {
+        let crate_dir = Path::new("does/not/day99");
+        let error = read_input(None, crate_dir).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "does/not/day99/input.txt: no input for day 99, run `aoc fetch 99` to download it"
+        );
+
+        let error = read_input(Some("does/not/exist.txt"), crate_dir)
+            .err()
+            .unwrap();
+        assert!(matches!(error, InputError::Io(..)));
+    }
+}
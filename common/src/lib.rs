@@ -0,0 +1,69 @@
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod client;
+pub mod input;
+pub mod output;
+pub mod parse;
+pub mod submit;
+
+use crate::parse::ParseError;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The answer to one part of a puzzle. Besides the value that gets typed
+/// into the website, it can carry the details it was computed from, e.g.
+/// the guard and the minute that day 4's answer is the product of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub value: String,
+    pub extra: Map<String, Value>,
+}
+
+impl Answer {
+    pub fn new<T: ToString>(value: T) -> Answer {
+        Answer {
+            value: value.to_string(),
+            extra: Map::new(),
+        }
+    }
+
+    pub fn with<T: Into<Value>>(mut self, key: &str, value: T) -> Answer {
+        self.extra.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// A single day's puzzle, split into parsing the input and solving both
+/// parts of it. The `aoc` runner only talks to days through this trait.
+pub trait Solution {
+    /// The parsed puzzle input that both parts work on.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Answer;
+    fn part_2(input: &Self::Input) -> Answer;
+}
+
+/// Parses `input` and solves both parts, along with how long each part took.
+pub fn solve<S: Solution>(input: &str) -> Result<Vec<(Answer, Duration)>, ParseError> {
+    let parsed = S::parse(input)?;
+
+    let parts: [fn(&S::Input) -> Answer; 2] = [S::part_1, S::part_2];
+    let answers = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = part(&parsed);
+            (answer, start.elapsed())
+        })
+        .collect();
+    Ok(answers)
+}
@@ -0,0 +1,89 @@
+use crate::input::Input;
+use crate::{solve, Answer, Solution};
+use serde_json::json;
+use std::env;
+use std::process;
+use std::time::Duration;
+
+/// How the binaries print their answers: for people, or as one JSON object
+/// per part and line for scripts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// Reads `--format <text|json>` from the command line, exiting on
+    /// anything else. Without the option the format is text.
+    pub fn from_args() -> Format {
+        let args: Vec<String> = env::args().collect();
+        let name = match args.iter().position(|arg| arg == "--format") {
+            Some(i) => args.get(i + 1).map(String::as_str).unwrap_or(""),
+            None => return Format::Text,
+        };
+
+        Format::from_name(name).unwrap_or_else(|| {
+            eprintln!("unknown format `{}`, expected `text` or `json`", name);
+            process::exit(1);
+        })
+    }
+}
+
+/// `elapsed` is in seconds. `extra` is always there, even if it's empty, so
+/// that consumers don't have to check for it.
+pub fn to_json(day: u32, part: u32, answer: &Answer, elapsed: Duration) -> String {
+    json!({
+        "day": day,
+        "part": part,
+        "answer": answer.value,
+        "elapsed": elapsed.as_secs_f64(),
+        "extra": answer.extra,
+    })
+    .to_string()
+}
+
+/// Solves both parts of `S` and prints them as JSON, exiting with a
+/// diagnostic if `input` can't be parsed.
+pub fn print_json<S: Solution>(day: u32, input: &Input) {
+    let answers = solve::<S>(&input.contents).unwrap_or_else(|e| input.exit_with(&e));
+
+    for (part, (answer, elapsed)) in (1..).zip(answers) {
+        println!("{}", to_json(day, part, &answer, elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choosing_the_format() {
+        assert_eq!(Format::from_name("json"), Some(Format::Json));
+        assert_eq!(Format::from_name("text"), Some(Format::Text));
+        assert_eq!(Format::from_name("yaml"), None);
+    }
+
+    #[test]
+    fn formatting_answers_as_json() {
+        let answer = Answer::new(240).with("guard", 10).with("minute", 24);
+        assert_eq!(
+            to_json(4, 1, &answer, Duration::from_millis(1500)),
+            r#"{"day":4,"part":1,"answer":"240","elapsed":1.5,"extra":{"guard":10,"minute":24}}"#
+        );
+
+        let answer = Answer::new("CABDFE");
+        assert_eq!(
+            to_json(7, 1, &answer, Duration::from_secs(0)),
+            r#"{"day":7,"part":1,"answer":"CABDFE","elapsed":0.0,"extra":{}}"#
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error in the puzzle input, pointing at the text that's wrong and
+/// describing what should have been there instead.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The complete line the error is on.
+    pub line_text: String,
+    /// The part of the line that couldn't be parsed.
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// `start` is the byte offset of `found` in `line_text`. Errors start out
+    /// on line 1; parsers working on whole inputs move them with `on_line`.
+    pub fn new(line_text: &str, start: usize, found: &str, expected: &str) -> ParseError {
+        ParseError {
+            line: 1,
+            column: line_text[..start].chars().count() + 1,
+            line_text: line_text.to_string(),
+            found: found.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// An error for a line that doesn't look like `expected` at all.
+    pub fn mismatch(line_text: &str, expected: &str) -> ParseError {
+        ParseError::new(line_text, 0, line_text, expected)
+    }
+
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    /// Renders the error like rustc does, with `source` naming the input:
+    ///
+    /// ```text
+    /// error: expected a number, found `x`
+    ///  --> input.txt:3:8
+    ///   |
+    /// 3 | #1 @ 1,x: 4x4
+    ///   |        ^
+    /// ```
+    pub fn diagnostic(&self, source: &dyn fmt::Display) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let carets = "^".repeat(self.found.chars().count().max(1));
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            source,
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            self.line_text,
+            gutter,
+            " ".repeat(self.column - 1),
+            carets
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.found.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found `{}`", self.expected, self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `field`, which starts at byte offset `start` of `line_text`.
+pub fn parse_field<T: FromStr>(
+    line_text: &str,
+    start: usize,
+    field: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    field
+        .parse::<T>()
+        .map_err(|_| ParseError::new(line_text, start, field, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_fields() {
+        let line = "#1 @ 1,x: 4x4";
+
+        assert_eq!(parse_field::<u32>(line, 5, "1", "a number"), Ok(1));
+
+        let error = parse_field::<u32>(line, 7, "x", "a number").unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.column, 8);
+        assert_eq!(error.found, "x");
+        assert_eq!(error.to_string(), "expected a number, found `x`");
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let error = ParseError::new("ä, x", 4, "x", "a number");
+        assert_eq!(error.column, 4);
+    }
+
+    #[test]
+    fn rendering_a_diagnostic() {
+        let error = ParseError::new("#1 @ 1,3: 4x-4", 12, "-4", "a width").on_line(12);
+
+        assert_eq!(
+            error.diagnostic(&"input.txt"),
+            [
+                "error: expected a width, found `-4`",
+                "  --> input.txt:12:13",
+                "   |",
+                "12 | #1 @ 1,3: 4x-4",
+                "   |             ^^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn rendering_a_diagnostic_for_missing_text() {
+        let error = ParseError::new("2 3 0", 5, "", "more numbers");
+
+        assert_eq!(
+            error.diagnostic(&"<stdin>"),
+            [
+                "error: expected more numbers, found nothing",
+                " --> <stdin>:1:6",
+                "  |",
+                "1 | 2 3 0",
+                "  |      ^",
+            ]
+            .join("\n")
+        );
+    }
+}
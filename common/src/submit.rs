@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// What the server said about a submitted answer.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently.
+    Wait(Option<Duration>),
+    /// The part has already been solved, or part 1 hasn't been yet.
+    WrongLevel,
+    /// Anything we don't recognize, with the text of the response.
+    Unknown(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "that's the right answer"),
+            Outcome::TooHigh => write!(f, "wrong, the answer is too high"),
+            Outcome::TooLow => write!(f, "wrong, the answer is too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(Some(wait)) => write!(
+                f,
+                "answered too recently, wait another {}m {}s",
+                wait.as_secs() / 60,
+                wait.as_secs() % 60
+            ),
+            Outcome::Wait(None) => write!(f, "answered too recently, wait a bit"),
+            Outcome::WrongLevel => write!(f, "not the right level, is it solved already?"),
+            Outcome::Unknown(text) => write!(f, "unexpected response: {}", text),
+        }
+    }
+}
+
+// Removes the HTML tags, which leaves the sentences we're looking for.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Parses "You have 1m 23s left to wait." and "You have 42s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left")?;
+
+    let mut seconds = 0;
+    for part in text[start..end].split_whitespace() {
+        let (number, unit) = part.split_at(part.len() - 1);
+        let number = number.parse::<u64>().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+pub fn parse_outcome(body: &str) -> Outcome {
+    // The interesting part of the page is in its only `<article>`.
+    let article = match (body.find("<article"), body.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &body[start..end],
+        _ => body,
+    };
+    let text = strip_tags(article);
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if text.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if text.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::Wait(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+/// Why an answer isn't even sent.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadyCorrect,
+    KnownCorrect(String),
+    KnownWrong,
+    AtLeast(String),
+    AtMost(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect => write!(f, "this answer was already accepted"),
+            Refusal::KnownCorrect(answer) => {
+                write!(f, "the part is solved already, the answer is {}", answer)
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already wrong before"),
+            Refusal::AtLeast(too_high) => {
+                write!(f, "{} was already too high, this is no lower", too_high)
+            }
+            Refusal::AtMost(too_low) => {
+                write!(f, "{} was already too low, this is no higher", too_low)
+            }
+        }
+    }
+}
+
+/// Everything that was submitted for one part.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Attempts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+    #[serde(default)]
+    pub wrong: Vec<String>,
+    #[serde(default)]
+    pub too_high: Vec<String>,
+    #[serde(default)]
+    pub too_low: Vec<String>,
+}
+
+impl Attempts {
+    fn numbers(answers: &[String]) -> impl Iterator<Item = i64> + '_ {
+        answers.iter().filter_map(|a| a.parse::<i64>().ok())
+    }
+
+    pub fn check(&self, answer: &str) -> Result<(), Refusal> {
+        match &self.correct {
+            Some(correct) if correct == answer => return Err(Refusal::AlreadyCorrect),
+            Some(correct) => return Err(Refusal::KnownCorrect(correct.clone())),
+            None => {}
+        }
+
+        let answer_is = |known: &Vec<String>| known.iter().any(|a| a == answer);
+        if answer_is(&self.wrong) || answer_is(&self.too_high) || answer_is(&self.too_low) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        // Bounds only make sense for numbers. Answers like "235,22" are
+        // never too high or too low.
+        let number = match answer.parse::<i64>() {
+            Ok(number) => number,
+            Err(_) => return Ok(()),
+        };
+        if let Some(too_high) = Attempts::numbers(&self.too_high).min() {
+            if number >= too_high {
+                return Err(Refusal::AtLeast(too_high.to_string()));
+            }
+        }
+        if let Some(too_low) = Attempts::numbers(&self.too_low).max() {
+            if number <= too_low {
+                return Err(Refusal::AtMost(too_low.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&mut self, answer: &str, outcome: &Outcome) {
+        let answer = answer.to_string();
+        match outcome {
+            Outcome::Correct => self.correct = Some(answer),
+            Outcome::TooHigh => self.too_high.push(answer),
+            Outcome::TooLow => self.too_low.push(answer),
+            Outcome::Wrong => self.wrong.push(answer),
+            // None of these say anything about the answer.
+            Outcome::Wait(_) | Outcome::WrongLevel | Outcome::Unknown(_) => {}
+        }
+    }
+}
+
+// day -> part -> attempts, e.g. `[day9.part_1] too_low = ["1337"]`
+type Registry = BTreeMap<String, BTreeMap<String, Attempts>>;
+
+/// The local history of submitted answers. It lives next to the cached
+/// inputs, since it belongs to the same account as they do.
+pub struct History {
+    path: PathBuf,
+    registry: Registry,
+}
+
+impl History {
+    pub fn path(cache_dir: &Path) -> PathBuf {
+        cache_dir
+            .join(crate::cache::YEAR.to_string())
+            .join("submissions.toml")
+    }
+
+    pub fn load(cache_dir: &Path) -> io::Result<History> {
+        let path = History::path(cache_dir);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let registry = toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+
+        Ok(History { path, registry })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents = toml::to_string(&self.registry)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        fs::write(&self.path, contents)
+    }
+
+    pub fn attempts(&mut self, day: u32, part: u32) -> &mut Attempts {
+        self.registry
+            .entry(format!("day{}", day))
+            .or_default()
+            .entry(format!("part_{}", part))
+            .or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn parsing_outcomes() {
+        assert_eq!(
+            parse_outcome(&page("That's the right answer!  You are <em>one gold star</em> closer to fixing the time stream. <a href=\"/2018/day/1#part2\">[Continue to Part Two]</a>")),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_outcome(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. <a href=\"/2018/day/9\">[Return to Day 9]</a>")),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_outcome(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_outcome(&page("That's not the right answer.  If you're stuck, there are some general tips on the <a href=\"/2018/about\">about page</a>.")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_outcome(&page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2018/day/3\">[Return to Day 3]</a>")),
+            Outcome::WrongLevel
+        );
+        assert_eq!(
+            parse_outcome("<html>Something else</html>"),
+            Outcome::Unknown(String::from("Something else"))
+        );
+    }
+
+    #[test]
+    fn parsing_how_long_to_wait() {
+        assert_eq!(
+            parse_outcome(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2018/day/4\">[Return to Day 4]</a>")),
+            Outcome::Wait(Some(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            parse_outcome(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 42s left to wait.")),
+            Outcome::Wait(Some(Duration::from_secs(42)))
+        );
+        assert_eq!(
+            parse_outcome(&page("You gave an answer too recently.")),
+            Outcome::Wait(None)
+        );
+    }
+
+    #[test]
+    fn refusing_known_wrong_answers() {
+        let mut attempts = Attempts::default();
+        assert_eq!(attempts.check("1000"), Ok(()));
+
+        attempts.record("1000", &Outcome::TooHigh);
+        attempts.record("2000", &Outcome::TooHigh);
+        attempts.record("100", &Outcome::TooLow);
+        attempts.record("500", &Outcome::Wrong);
+        attempts.record("600", &Outcome::Wait(None));
+
+        assert_eq!(attempts.check("1000"), Err(Refusal::KnownWrong));
+        assert_eq!(attempts.check("500"), Err(Refusal::KnownWrong));
+        assert_eq!(
+            attempts.check("1500"),
+            Err(Refusal::AtLeast(String::from("1000")))
+        );
+        assert_eq!(
+            attempts.check("99"),
+            Err(Refusal::AtMost(String::from("100")))
+        );
+        assert_eq!(attempts.check("600"), Ok(()));
+        assert_eq!(attempts.check("235,22"), Ok(()));
+
+        attempts.record("600", &Outcome::Correct);
+        assert_eq!(attempts.check("600"), Err(Refusal::AlreadyCorrect));
+        assert_eq!(
+            attempts.check("601"),
+            Err(Refusal::KnownCorrect(String::from("600")))
+        );
+    }
+
+    #[test]
+    fn history_roundtrip() {
+        let cache_dir = env::temp_dir().join(format!("aoc-submit-{}", process::id()));
+
+        let mut history = History::load(&cache_dir).unwrap();
+        history.attempts(9, 1).record("1337", &Outcome::TooLow);
+        history.attempts(9, 2).record("42", &Outcome::Correct);
+        history.save().unwrap();
+
+        let mut history = History::load(&cache_dir).unwrap();
+        assert_eq!(history.attempts(9, 1).too_low, vec![String::from("1337")]);
+        assert_eq!(history.attempts(9, 2).correct, Some(String::from("42")));
+        assert_eq!(*history.attempts(1, 1), Attempts::default());
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
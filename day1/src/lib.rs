@@ -0,0 +1,165 @@
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use std::collections::{HashMap, HashSet};
+
+pub fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            parse_field::<i64>(line, 0, line, "a frequency change like `+7` or `-3`")
+                .map_err(|e| e.on_line(i + 1))
+        })
+        .collect()
+}
+
+pub fn resulting_frequency(changes: &[i64]) -> i64 {
+    changes.iter().sum()
+}
+
+/// Applies `changes` over and over again, starting at 0, and returns the
+/// first frequency that's reached twice, if there is one.
+///
+/// Instead of cycling through the list until that happens, which can take
+/// hundreds of thousands of passes, this only walks the list once. After the
+/// first pass every frequency `f` of it comes back as `f + k * total` in pass
+/// `k`, so it repeats exactly when it lands on a frequency `g` of the first
+/// pass with `g - f` being a positive multiple of `total`.
+pub fn first_repeated_frequency(changes: &[i64]) -> Option<i64> {
+    // The frequencies of the first pass, before each change is applied.
+    let mut frequencies = Vec::with_capacity(changes.len());
+    let mut seen = HashSet::new();
+    let mut frequency = 0;
+    for change in changes {
+        if !seen.insert(frequency) {
+            return Some(frequency);
+        }
+        frequencies.push(frequency);
+        frequency += change;
+    }
+
+    let total = frequency;
+    if frequencies.is_empty() {
+        return None;
+    }
+    if total == 0 {
+        // Every pass is the same as the first one, so the start comes first.
+        return Some(0);
+    }
+
+    // Only frequencies with the same remainder can ever reach each other.
+    let mut by_remainder: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (i, &f) in frequencies.iter().enumerate() {
+        by_remainder
+            .entry(f.rem_euclid(total))
+            .or_default()
+            .push((f, i));
+    }
+
+    // A frequency first reaches its next neighbour in the direction of
+    // `total`. Going from `f` at index `i` to `g` takes `(g - f) / total`
+    // passes, so it happens at step `passes * len + i`.
+    let mut repeats = Vec::new();
+    for group in by_remainder.values_mut() {
+        group.sort_unstable();
+        if total < 0 {
+            group.reverse();
+        }
+
+        for pair in group.windows(2) {
+            let ((f, i), (g, _)) = (pair[0], pair[1]);
+            repeats.push(((g - f) / total, i, g));
+        }
+    }
+
+    repeats.into_iter().min().map(|(_, _, frequency)| frequency)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(changes: &Vec<i64>) -> Answer {
+        Answer::new(resulting_frequency(changes))
+    }
+
+    fn part_2(changes: &Vec<i64>) -> Answer {
+        match first_repeated_frequency(changes) {
+            Some(frequency) => Answer::new(frequency),
+            None => Answer::new("no frequency is reached twice"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Applies the changes one at a time, like the puzzle describes it.
+    fn cycle_until_repeated(changes: &[i64]) -> i64 {
+        let mut seen = HashSet::new();
+        let mut frequency = 0;
+        for change in changes.iter().cycle() {
+            if !seen.insert(frequency) {
+                return frequency;
+            }
+            frequency += change;
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_parsing_input() {
+        assert_eq!(parse_input("+1\n-2\n+3\n+1"), Ok(vec![1, -2, 3, 1]));
+
+        let error = parse_input("+1\n-2\n3+").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.found, "3+");
+    }
+
+    #[test]
+    fn test_resulting_frequency() {
+        assert_eq!(resulting_frequency(&[1, -2, 3, 1]), 3);
+        assert_eq!(resulting_frequency(&[1, 1, 1]), 3);
+        assert_eq!(resulting_frequency(&[1, 1, -2]), 0);
+        assert_eq!(resulting_frequency(&[-1, -2, -3]), -6);
+    }
+
+    #[test]
+    fn test_first_repeated_frequency() {
+        assert_eq!(first_repeated_frequency(&[1, -2, 3, 1]), Some(2));
+        assert_eq!(first_repeated_frequency(&[1, -1]), Some(0));
+        assert_eq!(first_repeated_frequency(&[3, 3, 4, -2, -4]), Some(10));
+        assert_eq!(first_repeated_frequency(&[-6, 3, 8, 5, -6]), Some(5));
+        assert_eq!(first_repeated_frequency(&[7, 7, -2, -7, -4]), Some(14));
+    }
+
+    #[test]
+    fn test_frequencies_that_never_repeat() {
+        assert_eq!(first_repeated_frequency(&[]), None);
+        assert_eq!(first_repeated_frequency(&[1]), None);
+        assert_eq!(first_repeated_frequency(&[1, 1, 1]), None);
+    }
+
+    #[test]
+    fn test_many_passes() {
+        // Reaches 100_000 only after 100_000 passes.
+        assert_eq!(first_repeated_frequency(&[100_000, -99_999]), Some(100_000));
+
+        let changes = [13, -7, 22, -25, 4, 9, -15, 0];
+        assert_eq!(
+            first_repeated_frequency(&changes),
+            Some(cycle_until_repeated(&changes))
+        );
+        let changes = [-13, 7, -22, 25, -4, -9, 15, 2];
+        assert_eq!(
+            first_repeated_frequency(&changes),
+            Some(cycle_until_repeated(&changes))
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use common::answers::Answers;
+use common::input;
+use common::output::{self, Format};
+use day1::*;
+use std::env;
+use std::process;
+
+fn main() -> Result<(), std::io::Error> {
+    let record = env::args().any(|arg| arg == "--record");
+    let input = input::from_args(env!("CARGO_MANIFEST_DIR"), &[]).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let contents = &input.contents;
+
+    if Format::from_args() == Format::Json {
+        output::print_json::<Day1>(1, &input);
+        return Ok(());
+    }
+
+    let mut answers = Answers::load()?;
+
+    let changes = parse_input(contents).unwrap_or_else(|e| input.exit_with(&e));
+
+    // Part 1
+    let frequency = resulting_frequency(&changes).to_string();
+    println!(
+        "resulting frequency={} ({})",
+        frequency,
+        answers.check(1, 1, contents, &frequency)
+    );
+
+    // Part 2
+    let repeated = match first_repeated_frequency(&changes) {
+        Some(repeated) => repeated.to_string(),
+        None => {
+            println!("no frequency is reached twice");
+            return Ok(());
+        }
+    };
+    println!(
+        "first frequency reached twice={} ({})",
+        repeated,
+        answers.check(1, 2, contents, &repeated)
+    );
+
+    if record {
+        answers.record(1, 1, contents, &frequency);
+        answers.record(1, 2, contents, &repeated);
+        answers.save()?;
+    }
+
+    Ok(())
+}
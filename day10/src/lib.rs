@@ -0,0 +1,256 @@
+extern crate regex;
+
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use grid::{Bounds, Point, SparseGrid};
+use regex::Regex;
+use std::fmt;
+
+pub fn parse_input<'a, I>(lines: I) -> Result<Vec<Position>, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let line_re: Regex =
+        Regex::new(r"position=<\s*(-?\d+),\s*(-?\d+)>\svelocity=<\s*(-?\d+),\s*(-?\d+)>").unwrap();
+    let mut positions = Vec::new();
+
+    for (i, line) in lines.enumerate() {
+        let caps = line_re.captures(line).ok_or_else(|| {
+            ParseError::mismatch(line, "a point like `position=< 9,  1> velocity=< 0,  2>`")
+                .on_line(i + 1)
+        })?;
+
+        let mut numbers = Vec::new();
+        for n in 1..=4 {
+            let field = caps.get(n).unwrap();
+            let number = parse_field::<i32>(line, field.start(), field.as_str(), "a number")
+                .map_err(|e| e.on_line(i + 1))?;
+            numbers.push(number);
+        }
+        let (x, y, velocity_x, velocity_y) = (numbers[0], numbers[1], numbers[2], numbers[3]);
+        positions.push(Position {
+            x,
+            y,
+            velocity_x,
+            velocity_y,
+        });
+    }
+
+    Ok(positions)
+}
+
+#[derive(Debug)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    pub velocity_x: i32,
+    pub velocity_y: i32,
+}
+
+impl Position {
+    pub fn point(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    fn tick(&mut self) {
+        self.x += self.velocity_x;
+        self.y += self.velocity_y;
+    }
+
+    fn untick(&mut self) {
+        self.x -= self.velocity_x;
+        self.y -= self.velocity_y;
+    }
+}
+
+pub struct Grid {
+    pub positions: Vec<Position>,
+    pub bounds: Bounds,
+}
+
+impl Grid {
+    /// Panics if there are no positions, since they wouldn't have bounds.
+    pub fn new(positions: Vec<Position>) -> Grid {
+        let bounds = Bounds::of(positions.iter().map(Position::point)).unwrap();
+        Grid { positions, bounds }
+    }
+
+    pub fn tick(&mut self) {
+        for p in self.positions.iter_mut() {
+            p.tick();
+        }
+        self.update_bounds();
+    }
+
+    fn untick(&mut self) {
+        for p in self.positions.iter_mut() {
+            p.untick();
+        }
+        self.update_bounds();
+    }
+
+    fn update_bounds(&mut self) {
+        self.bounds = Bounds::of(self.positions.iter().map(Position::point)).unwrap();
+    }
+
+    // The positions converge into the message and then drift apart again, so
+    // the message is visible in the second with the smallest bounding box.
+    pub fn find_message(&mut self) -> u32 {
+        let mut second = 0;
+
+        loop {
+            let size = self.bounds.width() + self.bounds.height();
+            self.tick();
+            if self.bounds.width() + self.bounds.height() > size {
+                self.untick();
+                return second;
+            }
+            second += 1;
+        }
+    }
+
+    pub fn draw(&self) {
+        print!("{}", self);
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut sky = SparseGrid::new();
+        for p in &self.positions {
+            sky.insert(p.point(), '#');
+        }
+        write!(f, "{}", sky)
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = (Grid, u32);
+
+    fn parse(input: &str) -> Result<(Grid, u32), ParseError> {
+        let positions = parse_input(input.lines())?;
+        if positions.is_empty() {
+            return Err(ParseError::mismatch("", "at least one point"));
+        }
+
+        let mut grid = Grid::new(positions);
+        let second = grid.find_message();
+        Ok((grid, second))
+    }
+
+    fn part_1((grid, _): &(Grid, u32)) -> Answer {
+        Answer::new(grid)
+    }
+
+    fn part_2((_, second): &(Grid, u32)) -> Answer {
+        Answer::new(second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_input<'a>() -> Vec<&'a str> {
+        include_str!("../example.txt").lines().collect()
+    }
+
+    #[test]
+    fn test_parsing_input() {
+        let input = test_input();
+        let positions = parse_input(input.into_iter()).unwrap();
+        assert_eq!(positions.len(), 31);
+        assert_eq!(positions[2].x, 3);
+        assert_eq!(positions[2].y, -2);
+        assert_eq!(positions[2].velocity_x, -1);
+        assert_eq!(positions[2].velocity_y, 1);
+    }
+
+    #[test]
+    fn test_parsing_bad_input() {
+        let input = vec![
+            "position=< 9,  1> velocity=< 0,  2>",
+            "position=< 7,  0> velocity=<-1 0>",
+        ];
+        let error = parse_input(input.into_iter()).unwrap_err();
+        assert_eq!(error.line, 2);
+
+        let input = vec!["position=< 9,  1> velocity=< 0, 99999999999>"];
+        let error = parse_input(input.into_iter()).unwrap_err();
+        assert_eq!(error.column, 33);
+        assert_eq!(error.found, "99999999999");
+    }
+
+    #[test]
+    fn test_moving_positions() {
+        let mut position = Position {
+            x: 3,
+            y: -2,
+            velocity_x: -1,
+            velocity_y: 1,
+        };
+
+        position.tick();
+
+        assert_eq!(position.x, 2);
+        assert_eq!(position.y, -1);
+        assert_eq!(position.velocity_x, -1);
+        assert_eq!(position.velocity_y, 1);
+
+        position.tick();
+
+        assert_eq!(position.x, 1);
+        assert_eq!(position.y, 0);
+        assert_eq!(position.velocity_x, -1);
+        assert_eq!(position.velocity_y, 1);
+    }
+
+    #[test]
+    fn test_grid_moving_all_positions() {
+        let positions = vec![
+            Position {
+                x: 3,
+                y: -2,
+                velocity_x: -1,
+                velocity_y: 1,
+            },
+            Position {
+                x: 3,
+                y: -2,
+                velocity_x: -1,
+                velocity_y: 1,
+            },
+        ];
+
+        let mut grid = Grid::new(positions);
+        grid.tick();
+
+        assert_eq!(grid.positions[0].x, 2);
+        assert_eq!(grid.positions[0].y, -1);
+        assert_eq!(grid.positions[1].x, 2);
+        assert_eq!(grid.positions[1].y, -1);
+    }
+
+    #[test]
+    fn test_finding_the_message() {
+        let input = test_input();
+        let mut grid = Grid::new(parse_input(input.into_iter()).unwrap());
+
+        assert_eq!(grid.find_message(), 3);
+        assert_eq!(grid.bounds.width(), 10);
+        assert_eq!(grid.bounds.height(), 8);
+        assert_eq!(
+            grid.to_string(),
+            "#...#..###\n\
+             #...#...#.\n\
+             #...#...#.\n\
+             #####...#.\n\
+             #...#...#.\n\
+             #...#...#.\n\
+             #...#...#.\n\
+             #...#..###\n"
+        );
+    }
+}
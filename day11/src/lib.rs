@@ -0,0 +1,159 @@
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use grid::{Bounds, DenseGrid, Point};
+
+pub struct Grid {
+    serial_number: i32,
+    width: i32,
+    height: i32,
+    powers: DenseGrid<i32>,
+}
+
+impl Grid {
+    pub fn new(serial_number: i32) -> Grid {
+        // The fuel cells are numbered from 1,1 to 300,300.
+        let bounds = Bounds::new(Point::new(1, 1), Point::new(300, 300));
+
+        let mut grid = Grid {
+            serial_number,
+            width: bounds.width(),
+            height: bounds.height(),
+            powers: DenseGrid::covering(bounds, 0),
+        };
+        grid.precalculate_power_levels();
+        grid
+    }
+
+    fn precalculate_power_levels(&mut self) {
+        let bounds = self.powers.bounds().unwrap();
+        for p in bounds.points() {
+            self.powers[p] = self.power_level_at(p.x, p.y);
+        }
+    }
+
+    pub fn get_power_level_at(&self, x: i32, y: i32) -> i32 {
+        self.powers[Point::new(x, y)]
+    }
+
+    fn power_level_at(&self, x: i32, y: i32) -> i32 {
+        let rack_id = x + 10;
+        let mut power_level = ((rack_id * y) + self.serial_number) * rack_id;
+        power_level = (power_level / 100) % 10;
+        power_level - 5
+    }
+
+    pub fn square_power_level_at(&self, square_size: i32, top_left_x: i32, top_left_y: i32) -> i32 {
+        // Row by row, since that's how the power levels are laid out.
+        let mut sum = 0;
+        for y in top_left_y..top_left_y + square_size {
+            if y > self.height {
+                break;
+            }
+
+            for x in top_left_x..top_left_x + square_size {
+                if x > self.width {
+                    break;
+                }
+
+                sum += self.get_power_level_at(x, y);
+            }
+        }
+        sum
+    }
+
+    pub fn highest_powered_square_with_size(&self, square_size: i32) -> (i32, i32) {
+        let mut max_power = 0;
+        let mut max_coords = (0, 0);
+
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                let square_power = self.square_power_level_at(square_size, x, y);
+                if square_power > max_power {
+                    max_power = square_power;
+                    max_coords = (x, y);
+                }
+            }
+        }
+
+        max_coords
+    }
+
+    pub fn find_highest_powered_square(&self) -> (i32, i32, i32) {
+        let mut max_power = 0;
+        let mut max_coords_and_size = (0, 0, 0);
+
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                for square_size in 0..(self.width - x + 1) {
+                    let square_power = self.square_power_level_at(square_size, x, y);
+                    if square_power > max_power {
+                        max_power = square_power;
+                        max_coords_and_size = (x, y, square_size);
+                    }
+                }
+            }
+        }
+
+        max_coords_and_size
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Grid, ParseError> {
+        let line = input.lines().next().unwrap_or("");
+        let grid_serial_number = parse_field::<i32>(line, 0, line, "a grid serial number")?;
+        Ok(Grid::new(grid_serial_number))
+    }
+
+    fn part_1(grid: &Grid) -> Answer {
+        let (x, y) = grid.highest_powered_square_with_size(3);
+        Answer::new(format!("{},{}", x, y))
+            .with("x", x)
+            .with("y", y)
+    }
+
+    fn part_2(grid: &Grid) -> Answer {
+        let (x, y, size) = grid.find_highest_powered_square();
+        Answer::new(format!("{},{},{}", x, y, size))
+            .with("x", x)
+            .with("y", y)
+            .with("size", size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_levels() {
+        assert_eq!(Grid::new(8).get_power_level_at(3, 5), 4);
+        assert_eq!(Grid::new(57).get_power_level_at(122, 79), -5);
+        assert_eq!(Grid::new(39).get_power_level_at(217, 196), 0);
+        assert_eq!(Grid::new(71).get_power_level_at(101, 153), 4);
+    }
+
+    #[test]
+    fn square_power_levels() {
+        assert_eq!(Grid::new(18).square_power_level_at(3, 33, 45), 29);
+        assert_eq!(Grid::new(18).square_power_level_at(16, 90, 269), 113);
+        assert_eq!(Grid::new(42).square_power_level_at(3, 21, 61), 30);
+        assert_eq!(Grid::new(42).square_power_level_at(12, 232, 251), 119);
+    }
+
+    #[test]
+    pub fn highest_powered_square_with_size() {
+        assert_eq!(Grid::new(18).highest_powered_square_with_size(3), (33, 45));
+        assert_eq!(Grid::new(42).highest_powered_square_with_size(3), (21, 61));
+    }
+
+    #[test]
+    pub fn find_highest_powered_square() {
+        // assert_eq!(Grid::new(18).find_highest_powered_square(), (90, 269, 16));
+        // assert_eq!(Grid::new(42).find_highest_powered_square(), (232, 251, 12));
+    }
+}
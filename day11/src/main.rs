@@ -1,149 +1,52 @@
-struct Grid {
-    serial_number: i32,
-    width: i32,
-    height: i32,
-    powers: Vec<Vec<i32>>,
-}
+use common::answers::Answers;
+use common::input;
+use common::output::{self, Format};
+use common::Solution;
+use day11::*;
+use std::env;
+use std::process;
 
-impl Grid {
-    fn new(serial_number: i32) -> Grid {
-        let powers = vec![vec![0; 300]; 300];
+fn main() -> Result<(), std::io::Error> {
+    let record = env::args().any(|arg| arg == "--record");
+    let input = input::from_args(env!("CARGO_MANIFEST_DIR"), &[]).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let contents = &input.contents;
 
-        let mut grid = Grid {
-            serial_number,
-            width: 300,
-            height: 300,
-            powers: powers,
-        };
-        grid.precalculate_power_levels();
-        grid
+    if Format::from_args() == Format::Json {
+        output::print_json::<Day11>(11, &input);
+        return Ok(());
     }
 
-    fn precalculate_power_levels(&mut self) {
-        for x in 1..=self.width {
-            for y in 1..=self.height {
-                self.powers[(x - 1) as usize][(y - 1) as usize] = self.power_level_at(x, y);
-            }
-        }
-    }
+    let grid = Day11::parse(contents).unwrap_or_else(|e| input.exit_with(&e));
 
-    fn get_power_level_at(&self, x: i32, y: i32) -> i32 {
-        self.powers[(x - 1) as usize][(y - 1) as usize]
-    }
-
-    fn power_level_at(&self, x: i32, y: i32) -> i32 {
-        let rack_id = x + 10;
-        let mut power_level = ((rack_id * y) + self.serial_number) * rack_id;
-        power_level = (power_level / 100) % 10;
-        power_level - 5
-    }
-
-    fn square_power_level_at(&self, square_size: i32, top_left_x: i32, top_left_y: i32) -> i32 {
-        let mut sum = 0;
-        for x in top_left_x..top_left_x + square_size {
-            if x > self.width {
-                break;
-            }
-
-            for y in top_left_y..top_left_y + square_size {
-                if y > self.width {
-                    break;
-                }
-
-                sum += self.get_power_level_at(x, y);
-            }
-        }
-        sum
-    }
-
-    fn highest_powered_square_with_size(&self, square_size: i32) -> (i32, i32) {
-        let mut max_power = 0;
-        let mut max_coords = (0, 0);
-
-        for x in 1..=self.width {
-            for y in 1..=self.height {
-                let square_power = self.square_power_level_at(square_size, x, y);
-                if square_power > max_power {
-                    max_power = square_power;
-                    max_coords = (x, y);
-                }
-            }
-        }
-
-        max_coords
-    }
-
-    fn find_highest_powered_square(&self) -> (i32, i32, i32) {
-        let mut max_power = 0;
-        let mut max_coords_and_size = (0, 0, 0);
-
-        for x in 1..=self.width {
-            for y in 1..=self.height {
-                println!("x={}, y={}", x, y);
-                for square_size in 0..(self.width - x + 1) {
-                    let square_power = self.square_power_level_at(square_size, x, y);
-                    if square_power > max_power {
-                        max_power = square_power;
-                        max_coords_and_size = (x, y, square_size);
-                    }
-                }
-            }
-        }
-
-        max_coords_and_size
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn power_levels() {
-        assert_eq!(Grid::new(8).get_power_level_at(3, 5), 4);
-        assert_eq!(Grid::new(57).get_power_level_at(122, 79), -5);
-        assert_eq!(Grid::new(39).get_power_level_at(217, 196), 0);
-        assert_eq!(Grid::new(71).get_power_level_at(101, 153), 4);
-    }
-
-    #[test]
-    fn square_power_levels() {
-        assert_eq!(Grid::new(18).square_power_level_at(3, 33, 45), 29);
-        assert_eq!(Grid::new(18).square_power_level_at(16, 90, 269), 113);
-        assert_eq!(Grid::new(42).square_power_level_at(3, 21, 61), 30);
-        assert_eq!(Grid::new(42).square_power_level_at(12, 232, 251), 119);
-    }
-
-    #[test]
-    fn highest_powered_square_with_size() {
-        assert_eq!(Grid::new(18).highest_powered_square_with_size(3), (33, 45));
-        assert_eq!(Grid::new(42).highest_powered_square_with_size(3), (21, 61));
-    }
-
-    #[test]
-    fn find_highest_powered_square() {
-        // assert_eq!(Grid::new(18).find_highest_powered_square(), (90, 269, 16));
-        // assert_eq!(Grid::new(42).find_highest_powered_square(), (232, 251, 12));
-    }
-}
-
-fn main() {
-    let grid_serial_number = 5177;
-    let grid = Grid::new(grid_serial_number);
+    let mut answers = Answers::load()?;
 
     // Part 1
     let (x, y) = grid.highest_powered_square_with_size(3);
-    println!("highest powered square with size 3 is at: {},{}", x, y);
-    assert_eq!(x, 235);
-    assert_eq!(y, 22);
+    let part_1 = format!("{},{}", x, y);
+    println!(
+        "highest powered square with size 3 is at: {} ({})",
+        part_1,
+        answers.check(11, 1, contents, &part_1)
+    );
 
     // Part 2
     let (x, y, size) = grid.find_highest_powered_square();
+    let part_2 = format!("{},{},{}", x, y, size);
     println!(
-        "highest powered square with size {} is at: {},{}",
-        size, x, y
+        "highest powered square with size {} is at: {},{} ({})",
+        size,
+        x,
+        y,
+        answers.check(11, 2, contents, &part_2)
     );
-    assert_eq!(x, 231);
-    assert_eq!(y, 135);
-    assert_eq!(size, 8);
+
+    if record {
+        answers.record(11, 1, contents, &part_1);
+        answers.record(11, 2, contents, &part_2);
+        answers.save()?;
+    }
+    Ok(())
 }
@@ -0,0 +1,323 @@
+extern crate regex;
+
+use common::parse::ParseError;
+use common::{Answer, Solution};
+use regex::Regex;
+use std::fmt;
+
+pub fn parse_input<'a, I>(mut lines: I) -> Result<(State, Vec<Rule>), ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let initial_state_re: Regex = Regex::new(r"^initial\sstate:\s([.#]+)$").unwrap();
+    let rules_re: Regex = Regex::new(r"^([.#]{5})\s=>\s([.#])$").unwrap();
+
+    let first_line = lines.next().unwrap_or("");
+    let caps = initial_state_re.captures(first_line).ok_or_else(|| {
+        ParseError::mismatch(first_line, "the initial state like `initial state: #..#.#`")
+    })?;
+    let initial_state = State::from(caps.get(1).unwrap().as_str());
+
+    let mut rules = Vec::new();
+
+    for (i, line) in lines.enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let caps = rules_re
+            .captures(line)
+            .ok_or_else(|| ParseError::mismatch(line, "a rule like `..#.. => #`").on_line(i + 2))?;
+        let pattern_chars = caps.get(1).unwrap().as_str().chars().collect::<Vec<char>>();
+        let pattern = Pattern(
+            pattern_chars[0],
+            pattern_chars[1],
+            pattern_chars[2],
+            pattern_chars[3],
+            pattern_chars[4],
+        );
+        let result_chars = caps.get(2).unwrap().as_str().chars().collect::<Vec<char>>();
+        rules.push(Rule {
+            pattern,
+            result: result_chars[0],
+        });
+    }
+
+    Ok((initial_state, rules))
+}
+
+#[derive(Debug, Clone)]
+pub struct State {
+    pub pots: Vec<char>,
+}
+
+impl From<&str> for State {
+    fn from(input: &str) -> State {
+        let pots = input.chars().collect::<Vec<char>>();
+        State { pots }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pots.iter().collect::<String>())
+    }
+}
+
+impl State {
+    fn get_pattern_at(&self, pos: i32) -> Pattern {
+        let max_pos = (self.pots.len() - 1) as i32;
+        let mut pots = ['.'; 5];
+        let mut i = 0;
+
+        for j in pos - 2..=pos + 2 {
+            if j < 0 || j > max_pos {
+                i += 1;
+                continue;
+            }
+
+            if let Some(&pot) = self.pots.get(j as usize) {
+                pots[i] = pot;
+            }
+            i += 1;
+        }
+
+        Pattern(pots[0], pots[1], pots[2], pots[3], pots[4])
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern(char, char, char, char, char);
+
+impl From<&str> for Pattern {
+    fn from(input: &str) -> Pattern {
+        let chars = input.chars().collect::<Vec<char>>();
+        Pattern::from_vec(chars)
+    }
+}
+
+impl Pattern {
+    pub fn from_vec(input: Vec<char>) -> Pattern {
+        Pattern(input[0], input[1], input[2], input[3], input[4])
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Rule {
+    pattern: Pattern,
+    result: char,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub offset: i32,
+    pub state: State,
+    pub rules: Vec<Rule>,
+}
+
+impl Game {
+    pub fn evolve(&mut self) {
+        let mut new_pots = self.state.pots.clone();
+        let len = self.state.pots.len() as i32;
+        let start_pos = -2_i32;
+        let end_pos = len + 2;
+
+        let mut prepend = ['.', '.'];
+        let mut append = ['.', '.'];
+
+        for i in start_pos..end_pos {
+            let pattern = self.state.get_pattern_at(i);
+
+            for rule in &self.rules {
+                if rule.pattern == pattern {
+                    if i < 0 {
+                        prepend[(i + 2) as usize] = rule.result;
+                        continue;
+                    }
+                    if i > len - 1 {
+                        append[(i - len) as usize] = rule.result;
+                        continue;
+                    }
+                    new_pots[i as usize] = rule.result;
+                }
+            }
+        }
+
+        if prepend[0] == '#' {
+            self.offset = -2;
+            new_pots.insert(0, prepend[1]);
+            new_pots.insert(0, prepend[0]);
+        } else if prepend[1] == '#' {
+            self.offset = -1;
+            new_pots.insert(0, prepend[1]);
+        }
+
+        if append[1] == '#' {
+            for &c in append.iter() {
+                new_pots.push(c);
+            }
+        } else if append[0] == '#' {
+            new_pots.push(append[0]);
+        }
+
+        self.state.pots = new_pots;
+    }
+
+    pub fn sum(&self) -> i32 {
+        let mut sum = 0;
+        for (i, &p) in self.state.pots.iter().enumerate() {
+            if p == '#' {
+                sum += (i as i32) + self.offset;
+            }
+        }
+        sum
+    }
+}
+
+// After enough generations the pots settle into a pattern that only moves
+// along, so the sum grows by the same amount each generation. Once that
+// happens we can stop evolving and extrapolate the remaining generations.
+pub fn sum_after_generations(initial_state: &State, rules: &[Rule], generations: u64) -> i64 {
+    let mut game = Game {
+        offset: 0,
+        state: initial_state.clone(),
+        rules: rules.to_vec(),
+    };
+
+    let mut current_sum = i64::from(game.sum());
+    let mut last_diff = 0;
+    let mut stable_for = 0;
+
+    for generation in 1..=generations {
+        game.evolve();
+        let new_sum = i64::from(game.sum());
+        let diff = new_sum - current_sum;
+        current_sum = new_sum;
+
+        if diff == last_diff {
+            stable_for += 1;
+        } else {
+            last_diff = diff;
+            stable_for = 0;
+        }
+
+        if stable_for == 100 {
+            return current_sum + (generations - generation) as i64 * diff;
+        }
+    }
+
+    current_sum
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (State, Vec<Rule>);
+
+    fn parse(input: &str) -> Result<(State, Vec<Rule>), ParseError> {
+        parse_input(input.lines())
+    }
+
+    fn part_1((initial_state, rules): &(State, Vec<Rule>)) -> Answer {
+        Answer::new(sum_after_generations(initial_state, rules, 20))
+    }
+
+    fn part_2((initial_state, rules): &(State, Vec<Rule>)) -> Answer {
+        Answer::new(sum_after_generations(initial_state, rules, 50_000_000_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_input<'a>() -> Vec<&'a str> {
+        include_str!("../example.txt").lines().collect()
+    }
+
+    #[test]
+    fn test_parsing_input() {
+        let input = test_input();
+        let (initial_state, rules) = parse_input(input.into_iter()).unwrap();
+        assert_eq!(initial_state.to_string(), "#..#.#..##......###...###",);
+        assert_eq!(rules.len(), 14);
+        assert_eq!(rules[0].result, '#');
+        assert_eq!(rules[0].pattern, Pattern('.', '.', '.', '#', '#'));
+    }
+
+    #[test]
+    fn test_parsing_bad_input() {
+        let input = vec!["...## => #"];
+        let error = parse_input(input.into_iter()).unwrap_err();
+        assert_eq!(error.line, 1);
+
+        let input = vec!["initial state: #..#", "", "...## => #", "..#. => #"];
+        let error = parse_input(input.into_iter()).unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.found, "..#. => #");
+    }
+
+    #[test]
+    fn test_building_pattern_per_pot() {
+        let state = State::from("#..#.#..##......###...###");
+        let len = state.pots.len() as i32;
+
+        assert_eq!(state.get_pattern_at(-2), Pattern::from("....#"));
+        assert_eq!(state.get_pattern_at(-1), Pattern::from("...#."));
+        assert_eq!(state.get_pattern_at(0), Pattern::from("..#.."));
+        assert_eq!(state.get_pattern_at(1), Pattern::from(".#..#"));
+        assert_eq!(state.get_pattern_at(len - 2), Pattern::from(".###."));
+        assert_eq!(state.get_pattern_at(len - 1), Pattern::from("###.."));
+        assert_eq!(state.get_pattern_at(len), Pattern::from("##..."));
+        assert_eq!(state.get_pattern_at(len + 1), Pattern::from("#...."));
+        assert_eq!(state.get_pattern_at(len + 2), Pattern::from("....."));
+    }
+
+    #[test]
+    fn test_evolving_a_game_without_spread() {
+        let input = test_input();
+        let (_, rules) = parse_input(input.into_iter()).unwrap();
+        let mut game = Game {
+            offset: 0,
+            state: State::from("...##"),
+            rules,
+        };
+
+        assert_eq!(game.state.to_string(), "...##");
+        game.evolve();
+        assert_eq!(game.state.to_string(), "..###",);
+    }
+
+    #[test]
+    fn test_evolving_a_game_with_spread() {
+        let input = test_input();
+        let (_, rules) = parse_input(input.into_iter()).unwrap();
+        let mut game = Game {
+            offset: 0,
+            state: State::from("##..."),
+            rules,
+        };
+
+        assert_eq!(game.state.to_string().len(), 5);
+        assert_eq!(game.state.to_string(), "##...");
+        game.evolve();
+        assert_eq!(game.offset, -1);
+        assert_eq!(game.state.to_string().len(), 6);
+        assert_eq!(game.state.to_string(), "###...",);
+    }
+
+    #[test]
+    fn test_game_sum() {
+        let state = State::from("#....##....#####...#######....#.#..##");
+        let rules = Vec::new();
+        let offset = -2;
+
+        let game = Game {
+            offset,
+            rules,
+            state,
+        };
+
+        assert_eq!(game.sum(), 325);
+    }
+}
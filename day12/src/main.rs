@@ -1,284 +1,27 @@
-extern crate regex;
-
-use regex::Regex;
+use common::answers::Answers;
+use common::input;
+use common::output::{self, Format};
+use day12::*;
 use std::env;
-use std::fs::File;
-use std::io::prelude::*;
 use std::process;
 
-fn parse_input<'a, I>(lines: I) -> (State, Vec<Rule>)
-where
-    I: Iterator<Item = &'a str>,
-{
-    let initial_state_re: Regex = Regex::new(r"initial\sstate:\s(\W+)").unwrap();
-    let rules_re: Regex = Regex::new(r"(\W+)\s=>\s(\W)").unwrap();
-
-    let mut initial_state = State { pots: Vec::new() };
-    let mut rules = Vec::new();
-
-    for line in lines {
-        if let Some(caps) = initial_state_re.captures(line) {
-            for c in caps.get(1).unwrap().as_str().chars() {
-                initial_state.pots.push(c);
-            }
-        }
-
-        if let Some(caps) = rules_re.captures(line) {
-            let pattern_chars = caps.get(1).unwrap().as_str().chars().collect::<Vec<char>>();
-            let pattern = Pattern(
-                pattern_chars[0],
-                pattern_chars[1],
-                pattern_chars[2],
-                pattern_chars[3],
-                pattern_chars[4],
-            );
-            let result_chars = caps.get(2).unwrap().as_str().chars().collect::<Vec<char>>();
-            rules.push(Rule {
-                pattern,
-                result: result_chars[0],
-            });
-        }
-    }
-
-    (initial_state, rules)
-}
-
-#[derive(Debug, Clone)]
-struct State {
-    pots: Vec<char>,
-}
-
-impl State {
-    fn from_str(input: &str) -> State {
-        let pots = input.chars().collect::<Vec<char>>();
-        State { pots }
-    }
-
-    fn to_string(&self) -> String {
-        self.pots.iter().collect::<String>()
-    }
-
-    fn get_pattern_at(&self, pos: i32) -> Pattern {
-        let max_pos = (self.pots.len() - 1) as i32;
-        let mut pots = ['.'; 5];
-        let mut i = 0;
-
-        for j in pos - 2..=pos + 2 {
-            if j < 0 || j > max_pos {
-                i += 1;
-                continue;
-            }
-
-            if let Some(&pot) = self.pots.get(j as usize) {
-                pots[i] = pot;
-            }
-            i += 1;
-        }
-
-        Pattern(pots[0], pots[1], pots[2], pots[3], pots[4])
-    }
-}
-
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct Pattern(char, char, char, char, char);
-
-impl Pattern {
-    fn from_str(input: &str) -> Pattern {
-        let chars = input.chars().collect::<Vec<char>>();
-        Pattern::from_vec(chars)
-    }
-
-    fn from_vec(input: Vec<char>) -> Pattern {
-        Pattern(input[0], input[1], input[2], input[3], input[4])
-    }
-}
-
-#[derive(Clone, Debug)]
-struct Rule {
-    pattern: Pattern,
-    result: char,
-}
-
-#[derive(Debug)]
-struct Game {
-    offset: i32,
-    state: State,
-    rules: Vec<Rule>,
-}
-
-impl Game {
-    fn evolve(&mut self) {
-        let mut new_pots = self.state.pots.clone();
-        let len = self.state.pots.len() as i32;
-        let start_pos = -2 as i32;
-        let end_pos = len + 2;
-
-        let mut prepend = ['.', '.'];
-        let mut append = ['.', '.'];
-
-        for i in start_pos..end_pos {
-            let pattern = self.state.get_pattern_at(i);
-
-            for rule in &self.rules {
-                if rule.pattern == pattern {
-                    if i < 0 {
-                        prepend[(i + 2) as usize] = rule.result;
-                        continue;
-                    }
-                    if i > len - 1 {
-                        append[(i - len) as usize] = rule.result;
-                        continue;
-                    }
-                    new_pots[i as usize] = rule.result;
-                }
-            }
-        }
-
-        if prepend[0] == '#' {
-            self.offset = -2;
-            new_pots.insert(0, prepend[1]);
-            new_pots.insert(0, prepend[0]);
-        } else if prepend[1] == '#' {
-            self.offset = -1;
-            new_pots.insert(0, prepend[1]);
-        }
-
-        if append[1] == '#' {
-            for &c in append.iter() {
-                new_pots.push(c);
-            }
-        } else if append[0] == '#' {
-            new_pots.push(append[0]);
-        }
-
-        self.state.pots = new_pots;
-    }
-
-    fn sum(&self) -> i32 {
-        let mut sum = 0;
-        for (i, &p) in self.state.pots.iter().enumerate() {
-            if p == '#' {
-                sum += (i as i32) + self.offset;
-            }
-        }
-        sum
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn test_input<'a>() -> Vec<&'a str> {
-        vec![
-            "initial state: #..#.#..##......###...###",
-            "",
-            "...## => #",
-            "..#.. => #",
-            ".#... => #",
-            ".#.#. => #",
-            ".#.## => #",
-            ".##.. => #",
-            ".#### => #",
-            "#.#.# => #",
-            "#.### => #",
-            "##.#. => #",
-            "##.## => #",
-            "###.. => #",
-            "###.# => #",
-            "####. => #",
-        ]
-    }
-
-    #[test]
-    fn test_parsing_input() {
-        let input = test_input();
-        let (initial_state, rules) = parse_input(input.into_iter());
-        assert_eq!(initial_state.to_string(), "#..#.#..##......###...###",);
-        assert_eq!(rules.len(), 14);
-        assert_eq!(rules[0].result, '#');
-        assert_eq!(rules[0].pattern, Pattern('.', '.', '.', '#', '#'));
-    }
-
-    #[test]
-    fn test_building_pattern_per_pot() {
-        let state = State::from_str("#..#.#..##......###...###");
-        let len = state.pots.len() as i32;
-
-        assert_eq!(state.get_pattern_at(-2), Pattern::from_str("....#"));
-        assert_eq!(state.get_pattern_at(-1), Pattern::from_str("...#."));
-        assert_eq!(state.get_pattern_at(0), Pattern::from_str("..#.."));
-        assert_eq!(state.get_pattern_at(1), Pattern::from_str(".#..#"));
-        assert_eq!(state.get_pattern_at(len - 2), Pattern::from_str(".###."));
-        assert_eq!(state.get_pattern_at(len - 1), Pattern::from_str("###.."));
-        assert_eq!(state.get_pattern_at(len), Pattern::from_str("##..."));
-        assert_eq!(state.get_pattern_at(len + 1), Pattern::from_str("#...."));
-        assert_eq!(state.get_pattern_at(len + 2), Pattern::from_str("....."));
-    }
-
-    #[test]
-    fn test_evolving_a_game_without_spread() {
-        let input = test_input();
-        let (_, rules) = parse_input(input.into_iter());
-        let mut game = Game {
-            offset: 0,
-            state: State::from_str("...##"),
-            rules,
-        };
-
-        assert_eq!(game.state.to_string(), "...##");
-        game.evolve();
-        assert_eq!(game.state.to_string(), "..###",);
-    }
-
-    #[test]
-    fn test_evolving_a_game_with_spread() {
-        let input = test_input();
-        let (_, rules) = parse_input(input.into_iter());
-        let mut game = Game {
-            offset: 0,
-            state: State::from_str("##..."),
-            rules,
-        };
-
-        assert_eq!(game.state.to_string().len(), 5);
-        assert_eq!(game.state.to_string(), "##...");
-        game.evolve();
-        assert_eq!(game.offset, -1);
-        assert_eq!(game.state.to_string().len(), 6);
-        assert_eq!(game.state.to_string(), "###...",);
-    }
-
-    #[test]
-    fn test_game_sum() {
-        let state = State::from_str("#....##....#####...#######....#.#..##");
-        let rules = Vec::new();
-        let offset = -2;
-
-        let game = Game {
-            offset,
-            rules,
-            state,
-        };
-
-        assert_eq!(game.sum(), 325);
-    }
-}
-
 fn main() -> Result<(), std::io::Error> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 {
-        eprintln!("not enough arguments");
+    let record = env::args().any(|arg| arg == "--record");
+    let input = input::from_args(env!("CARGO_MANIFEST_DIR"), &[]).unwrap_or_else(|e| {
+        eprintln!("{}", e);
         process::exit(1);
+    });
+    let contents = &input.contents;
+
+    if Format::from_args() == Format::Json {
+        output::print_json::<Day12>(12, &input);
+        return Ok(());
     }
 
-    let filename = args[1].clone();
-    let mut f = File::open(filename)?;
-    let mut contents = String::new();
-    f.read_to_string(&mut contents)?;
+    let mut answers = Answers::load()?;
 
-    let (initial_state, rules) = parse_input(contents.lines());
+    let (initial_state, rules) =
+        parse_input(contents.lines()).unwrap_or_else(|e| input.exit_with(&e));
 
     // Part 1
     let mut game = Game {
@@ -286,14 +29,17 @@ fn main() -> Result<(), std::io::Error> {
         state: initial_state.clone(),
         rules: rules.clone(),
     };
-    println!("0: {}", game.state.to_string());
+    println!("0: {}", game.state);
     for i in 1..=20 {
         game.evolve();
-        println!("{}: {}", i, game.state.to_string());
+        println!("{}: {}", i, game.state);
     }
-    let part_1_sum = game.sum();
-    println!("sum={}", part_1_sum);
-    assert_eq!(part_1_sum, 1623);
+    let part_1_sum = game.sum().to_string();
+    println!(
+        "sum={} ({})",
+        part_1_sum,
+        answers.check(12, 1, contents, &part_1_sum)
+    );
 
     // Part 2
     let mut game = Game {
@@ -308,23 +54,24 @@ fn main() -> Result<(), std::io::Error> {
         let diff = new_sum - current_sum;
         current_sum = new_sum;
         println!("{}: sum={}, diff={}", i, new_sum, diff);
-
-        // After running this and looking at the output, there emerges a pattern
-        // after 1xx generations. Let's put an assertion on that:
-        //
-        if i == 500 {
-            assert_eq!(current_sum, 16401);
-            assert_eq!(diff, 32);
-        }
     }
 
-    // Since the sum for the 1000th generation is sum_of_500th_gen + 500*32
-    // we can calculate that the sum for the 50000000000th generation is
-    // sum_of_500th_gen + (50000000000 - 500)*32
-    // 16401 + (50000000000 - 500)*32
-    // -> 1600000000401
-    let part_2_sum = game.sum();
-    println!("sum={}", part_2_sum);
+    // After running this and looking at the output, there emerges a pattern
+    // after 1xx generations: the sum grows by the same amount every
+    // generation. `sum_after_generations` waits for that and extrapolates the
+    // sum for the 50000000000th generation from there.
+    let part_2_sum = sum_after_generations(&initial_state, &rules, 50_000_000_000).to_string();
+    println!(
+        "sum={} ({})",
+        part_2_sum,
+        answers.check(12, 2, contents, &part_2_sum)
+    );
+
+    if record {
+        answers.record(12, 1, contents, &part_1_sum);
+        answers.record(12, 2, contents, &part_2_sum);
+        answers.save()?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,566 @@
+use common::parse::ParseError;
+use common::{Answer, Solution};
+use grid::{DenseGrid, Point};
+use std::fmt;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '>' => Some(Direction::Right),
+            '<' => Some(Direction::Left),
+            'v' => Some(Direction::Down),
+            '^' => Some(Direction::Up),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum IntersectionDirection {
+    Left,
+    Straight,
+    Right,
+}
+
+impl fmt::Display for IntersectionDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::IntersectionDirection::*;
+
+        let output = match self {
+            Left => "LEFT",
+            Straight => "STRAIGHT",
+            Right => "RIGHT",
+        };
+        write!(f, "{}", output)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Cart {
+    pub direction: Direction,
+    pub intersection_direction: IntersectionDirection,
+}
+
+impl Cart {
+    fn new(direction: Direction) -> Cart {
+        Cart {
+            direction,
+            intersection_direction: IntersectionDirection::Left,
+        }
+    }
+
+    fn direction_on(&self, track: Option<TrackElement>) -> Option<Direction> {
+        use self::Direction::*;
+        use self::TrackElement::*;
+
+        match track {
+            Some(Horizontal) => Some(self.direction.clone()),
+            Some(Vertical) => Some(self.direction.clone()),
+            Some(TopRightToLeftBottom) => match self.direction {
+                Up => Some(Right),
+                Down => Some(Left),
+                Right => Some(Up),
+                Left => Some(Down),
+            },
+            Some(TopLeftToBottomRight) => match self.direction {
+                Up => Some(Left),
+                Down => Some(Right),
+                Left => Some(Up),
+                Right => Some(Down),
+            },
+            Some(Intersection) => match self.intersection_direction {
+                IntersectionDirection::Straight => Some(self.direction.clone()),
+                IntersectionDirection::Left => match self.direction {
+                    Up => Some(Left),
+                    Down => Some(Right),
+                    Left => Some(Down),
+                    Right => Some(Up),
+                },
+                IntersectionDirection::Right => match self.direction {
+                    Up => Some(Right),
+                    Down => Some(Left),
+                    Left => Some(Up),
+                    Right => Some(Down),
+                },
+            },
+            _ => None,
+        }
+    }
+
+    fn intersection_direction_on(
+        &self,
+        track: Option<TrackElement>,
+    ) -> Option<IntersectionDirection> {
+        use self::TrackElement::*;
+
+        match track {
+            Some(Horizontal) => Some(self.intersection_direction.clone()),
+            Some(Vertical) => Some(self.intersection_direction.clone()),
+            Some(TopRightToLeftBottom) => Some(self.intersection_direction.clone()),
+            Some(TopLeftToBottomRight) => Some(self.intersection_direction.clone()),
+            Some(Intersection) => match self.intersection_direction {
+                IntersectionDirection::Left => Some(IntersectionDirection::Straight),
+                IntersectionDirection::Straight => Some(IntersectionDirection::Right),
+                IntersectionDirection::Right => Some(IntersectionDirection::Left),
+            },
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Cart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let car_format = match self.direction {
+            Direction::Up => "^",
+            Direction::Down => "v",
+            Direction::Left => "<",
+            Direction::Right => ">",
+        };
+        write!(f, "{}", car_format)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum TrackElement {
+    Horizontal,
+    Vertical,
+    TopLeftToBottomRight,
+    TopRightToLeftBottom,
+    Intersection,
+}
+
+impl fmt::Display for TrackElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let track_format = match self {
+            TrackElement::Horizontal => "-",
+            TrackElement::Vertical => "|",
+            TrackElement::TopRightToLeftBottom => "/",
+            TrackElement::TopLeftToBottomRight => "\\",
+            TrackElement::Intersection => "+",
+        };
+        write!(f, "{}", track_format)
+    }
+}
+
+pub type Tracks = DenseGrid<Option<TrackElement>>;
+pub type Carts = DenseGrid<Option<Cart>>;
+
+#[derive(Clone, Debug)]
+pub struct Grid {
+    pub tracks: Tracks,
+    pub carts: Carts,
+    pub num_carts: u32,
+}
+
+fn interpolate_track_element(tracks: &Tracks, p: Point) -> Option<TrackElement> {
+    // Carts in the first row or column have nothing to the left or above them.
+    let left = tracks
+        .get(Point::new(p.x - 1, p.y))
+        .and_then(Option::as_ref);
+    match left {
+        None | Some(TrackElement::Vertical) => {}
+        Some(_) => return Some(TrackElement::Horizontal),
+    }
+
+    let up = tracks
+        .get(Point::new(p.x, p.y - 1))
+        .and_then(Option::as_ref);
+    if let Some(up) = up {
+        return match up {
+            TrackElement::Vertical
+            | TrackElement::TopRightToLeftBottom
+            | TrackElement::TopLeftToBottomRight
+            | TrackElement::Intersection => Some(TrackElement::Vertical),
+            _ => None,
+        };
+    }
+
+    None
+}
+
+impl Grid {
+    pub fn from_string(grid_string: String) -> Result<Grid, ParseError> {
+        let mut height = 0;
+        let mut width = 0;
+
+        for line in grid_string.lines() {
+            let line_width = line.chars().count();
+            if line_width > width {
+                width = line_width;
+            }
+
+            height += 1;
+        }
+
+        let mut tracks = DenseGrid::new(width, height, None);
+        let mut carts = DenseGrid::new(width, height, None);
+
+        let mut num_carts = 0;
+
+        for (y, line) in grid_string.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let p = Point::new(x as i32, y as i32);
+                let track_element = match c {
+                    '|' => Some(TrackElement::Vertical),
+                    '-' => Some(TrackElement::Horizontal),
+                    '+' => Some(TrackElement::Intersection),
+                    '\\' => Some(TrackElement::TopLeftToBottomRight),
+                    '/' => Some(TrackElement::TopRightToLeftBottom),
+                    '>' | '<' | 'v' | '^' => {
+                        let element = interpolate_track_element(&tracks, p);
+                        if element.is_none() {
+                            let start = line.char_indices().nth(x).unwrap().0;
+                            return Err(ParseError::new(
+                                line,
+                                start,
+                                &c.to_string(),
+                                "a cart with track to its left or above it",
+                            )
+                            .on_line(y + 1));
+                        }
+                        element
+                    }
+                    ' ' => None,
+                    _ => {
+                        let start = line.char_indices().nth(x).unwrap().0;
+                        return Err(ParseError::new(
+                            line,
+                            start,
+                            &c.to_string(),
+                            "one of `|-/\\+<>^v` or a space",
+                        )
+                        .on_line(y + 1));
+                    }
+                };
+                if let Some(element) = track_element {
+                    tracks[p].replace(element);
+                }
+
+                if let Some(direction) = Direction::from_char(c) {
+                    carts[p].replace(Cart::new(direction));
+                    num_carts += 1;
+                }
+            }
+        }
+
+        Ok(Grid {
+            tracks,
+            carts,
+            num_carts,
+        })
+    }
+
+    /// Moves every cart one step, row by row. Returns where the first
+    /// crash happened if `stop_on_crash` is set.
+    pub fn move_carts(&mut self, stop_on_crash: bool) -> Result<(), Point> {
+        use self::Direction::*;
+
+        let mut new_carts = self.carts.clone();
+        for p in self.carts.points() {
+            if let Some(cart) = &self.carts[p] {
+                let new_p = match cart.direction {
+                    Up => Point::new(p.x, p.y - 1),
+                    Down => Point::new(p.x, p.y + 1),
+                    Left => Point::new(p.x - 1, p.y),
+                    Right => Point::new(p.x + 1, p.y),
+                };
+
+                if new_carts[new_p].is_some() {
+                    new_carts[p] = None;
+                    new_carts[new_p] = None;
+
+                    self.carts[new_p] = None;
+                    self.num_carts -= 2;
+
+                    if stop_on_crash {
+                        return Err(new_p);
+                    } else {
+                        continue;
+                    }
+                }
+
+                let new_direction = cart.direction_on(self.tracks[new_p].clone());
+                if new_direction.is_none() {
+                    panic!("Cart runs off track at {} (origin: {})", new_p, p);
+                }
+
+                let intersection_direction =
+                    cart.intersection_direction_on(self.tracks[new_p].clone());
+                if intersection_direction.is_none() {
+                    panic!("Cart ran off track");
+                };
+
+                new_carts[p] = None;
+                new_carts[new_p].replace(Cart {
+                    direction: new_direction.unwrap(),
+                    intersection_direction: intersection_direction.unwrap(),
+                });
+            }
+        }
+
+        self.carts = new_carts;
+        Ok(())
+    }
+
+    pub fn cart_positions(&self) -> Vec<Point> {
+        self.carts
+            .iter()
+            .filter(|(_, cart)| cart.is_some())
+            .map(|(p, _)| p)
+            .collect()
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = self.tracks.rows().zip(self.carts.rows());
+        for (y, (tracks, carts)) in rows.enumerate() {
+            write!(f, "[{}]\t", y)?;
+
+            for (track, cart) in tracks.iter().zip(carts) {
+                if let Some(cart) = cart {
+                    write!(f, "{}", cart)?;
+                    continue;
+                };
+
+                if let Some(track) = track {
+                    write!(f, "{}", track)?;
+                    continue;
+                };
+
+                write!(f, " ")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Grid, ParseError> {
+        Grid::from_string(input.to_string())
+    }
+
+    fn part_1(grid: &Grid) -> Answer {
+        let mut grid = grid.clone();
+        loop {
+            if let Err(crash) = grid.move_carts(true) {
+                return Answer::new(crash).with("x", crash.x).with("y", crash.y);
+            }
+        }
+    }
+
+    fn part_2(grid: &Grid) -> Answer {
+        let mut grid = grid.clone();
+        while grid.num_carts > 1 {
+            if let Err(crash) = grid.move_carts(false) {
+                return Answer::new(format!("unexpected crash at {}", crash))
+                    .with("x", crash.x)
+                    .with("y", crash.y);
+            }
+        }
+        match grid.cart_positions().first() {
+            Some(&cart) => Answer::new(cart).with("x", cart.x).with("y", cart.y),
+            None => Answer::new("no cart left"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../example.txt");
+
+    fn row<T>(grid: &DenseGrid<T>, y: usize) -> &[T] {
+        grid.rows().nth(y).unwrap()
+    }
+
+    #[test]
+    fn determine_width_height() {
+        let grid = Grid::from_string(String::from(INPUT)).unwrap();
+        assert_eq!(grid.tracks.width(), 13);
+        assert_eq!(grid.tracks.height(), 7);
+    }
+
+    #[test]
+    fn parsing_bad_grids() {
+        let error = Grid::from_string(String::from("/->-\\\n|   |\n\\-x-/")).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.found, "x");
+
+        let error = Grid::from_string(String::from(">-\\\n  |")).unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.found, ">");
+    }
+
+    #[test]
+    fn parsing_track_elements() {
+        let grid = Grid::from_string(String::from(INPUT)).unwrap();
+
+        assert_eq!(
+            grid.tracks[Point::new(0, 0)],
+            Some(TrackElement::TopRightToLeftBottom)
+        );
+        assert_eq!(
+            grid.tracks[Point::new(1, 0)],
+            Some(TrackElement::Horizontal)
+        );
+        assert_eq!(
+            grid.tracks[Point::new(3, 0)],
+            Some(TrackElement::Horizontal)
+        );
+        assert_eq!(
+            grid.tracks[Point::new(4, 0)],
+            Some(TrackElement::TopLeftToBottomRight)
+        );
+
+        assert_eq!(
+            row(&grid.tracks, 1)[0..=5],
+            vec![
+                Some(TrackElement::Vertical),
+                None,
+                None,
+                None,
+                Some(TrackElement::Vertical),
+                None
+            ][..]
+        );
+
+        assert_eq!(
+            row(&grid.tracks, 2)[0..=7],
+            vec![
+                Some(TrackElement::Vertical),
+                None,
+                Some(TrackElement::TopRightToLeftBottom),
+                Some(TrackElement::Horizontal),
+                Some(TrackElement::Intersection),
+                Some(TrackElement::Horizontal),
+                Some(TrackElement::Horizontal),
+                Some(TrackElement::Intersection),
+            ][..]
+        );
+    }
+
+    #[test]
+    fn parsing_cart_positions() {
+        let grid = Grid::from_string(String::from(INPUT)).unwrap();
+
+        assert_eq!(
+            grid.carts[Point::new(2, 0)],
+            Some(Cart::new(Direction::Right))
+        );
+        assert!(row(&grid.carts, 1)[..].iter().all(|c| c.is_none()));
+        assert!(row(&grid.carts, 2)[..].iter().all(|c| c.is_none()));
+        assert_eq!(
+            grid.carts[Point::new(9, 3)],
+            Some(Cart::new(Direction::Down))
+        );
+        assert!(row(&grid.carts, 4)[..].iter().all(|c| c.is_none()));
+        assert!(row(&grid.carts, 5)[..].iter().all(|c| c.is_none()));
+        assert!(row(&grid.carts, 6)[..].iter().all(|c| c.is_none()));
+    }
+
+    #[test]
+    fn interpolating_track_elements_underneath_carts() {
+        let grid = Grid::from_string(String::from(INPUT)).unwrap();
+
+        assert_eq!(
+            grid.tracks[Point::new(2, 0)],
+            Some(TrackElement::Horizontal)
+        );
+        assert_eq!(grid.tracks[Point::new(9, 3)], Some(TrackElement::Vertical));
+    }
+
+    #[test]
+    fn moving_carts_on_grid() {
+        let mut grid = Grid::from_string(String::from(INPUT)).unwrap();
+
+        //    /->-\
+        //    |   |  /----\
+        //    | /-+--+-\  |
+        //    | | |  | v  |
+        //    \-+-/  \-+--/
+        //      \------/
+
+        assert_eq!(
+            grid.carts[Point::new(2, 0)],
+            Some(Cart::new(Direction::Right))
+        );
+        assert_eq!(
+            grid.carts[Point::new(9, 3)],
+            Some(Cart::new(Direction::Down))
+        );
+
+        assert!(grid.move_carts(true).is_ok());
+
+        //    /-->\
+        //    |   |  /----\
+        //    | /-+--+-\  |
+        //    | | |  | |  |
+        //    \-+-/  \->--/
+        //      \------/
+        assert_eq!(
+            grid.carts[Point::new(3, 0)],
+            Some(Cart::new(Direction::Right))
+        );
+        assert_eq!(
+            grid.carts[Point::new(9, 4)].clone().unwrap().direction,
+            Direction::Right
+        );
+        assert_eq!(
+            grid.carts[Point::new(9, 4)]
+                .clone()
+                .unwrap()
+                .intersection_direction,
+            IntersectionDirection::Straight
+        );
+
+        for _ in 0..12 {
+            assert!(grid.move_carts(true).is_ok());
+        }
+
+        assert_eq!(grid.move_carts(true), Err(Point::new(7, 3)));
+    }
+
+    #[test]
+    fn moving_carts_until_one_is_left() {
+        let crash_input = r#"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/"#;
+
+        let mut grid = Grid::from_string(String::from(crash_input)).unwrap();
+
+        loop {
+            let result = grid.move_carts(false);
+            assert!(result.is_ok());
+            if grid.num_carts == 1 {
+                break;
+            }
+        }
+
+        let cart_positions = grid.cart_positions();
+        assert_eq!(cart_positions.len(), 1);
+        assert_eq!(cart_positions.first(), Some(&Point::new(6, 4)));
+    }
+}
@@ -0,0 +1,128 @@
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+
+pub fn calculate_recipes(recipes: &mut Vec<i32>, warmup_num: usize, num: usize) -> Vec<i32> {
+    let mut result = Vec::with_capacity(num);
+
+    let mut index_1 = 0;
+    let mut index_2 = 1;
+
+    while result.len() < num {
+        let sum = recipes[index_1] + recipes[index_2];
+        let digits: Vec<i32> = sum
+            .to_string()
+            .chars()
+            .map(|d| d.to_digit(10).unwrap() as i32)
+            .collect();
+
+        for &d in &digits {
+            if recipes.len() >= warmup_num {
+                result.push(d);
+                if result.len() == num {
+                    break;
+                }
+            }
+            recipes.push(d);
+        }
+
+        index_1 = (index_1 + recipes[index_1] as usize + 1) % recipes.len();
+        index_2 = (index_2 + recipes[index_2] as usize + 1) % recipes.len();
+    }
+
+    result
+}
+
+pub fn first_occurrence(recipes: &mut Vec<i32>, pattern: Vec<i32>) -> usize {
+    let p_len = pattern.len();
+
+    let mut index_1 = 0;
+    let mut index_2 = 1;
+
+    loop {
+        let sum = recipes[index_1] + recipes[index_2];
+        let digits: Vec<i32> = sum
+            .to_string()
+            .chars()
+            .map(|d| d.to_digit(10).unwrap() as i32)
+            .collect();
+
+        for &d in &digits {
+            recipes.push(d);
+            let r_len = recipes.len();
+            if r_len >= p_len && recipes[r_len - p_len..] == pattern[..] {
+                return r_len - p_len;
+            }
+        }
+
+        index_1 = (index_1 + recipes[index_1] as usize + 1) % recipes.len();
+        index_2 = (index_2 + recipes[index_2] as usize + 1) % recipes.len();
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    // The input is both the number of warm-up recipes (part 1) and the
+    // sequence of scores to look for (part 2).
+    type Input = (usize, Vec<i32>);
+
+    fn parse(input: &str) -> Result<(usize, Vec<i32>), ParseError> {
+        let line = input.lines().next().unwrap_or("");
+        if let Some((start, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(ParseError::new(line, start, &c.to_string(), "a digit"));
+        }
+
+        let warmup_num = parse_field::<usize>(line, 0, line, "a number of recipes")?;
+        let pattern = line
+            .chars()
+            .map(|d| d.to_digit(10).unwrap() as i32)
+            .collect();
+        Ok((warmup_num, pattern))
+    }
+
+    fn part_1(&(warmup_num, _): &(usize, Vec<i32>)) -> Answer {
+        let scores: String = calculate_recipes(&mut vec![3, 7], warmup_num, 10)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        Answer::new(scores)
+    }
+
+    fn part_2((_, pattern): &(usize, Vec<i32>)) -> Answer {
+        Answer::new(first_occurrence(&mut vec![3, 7], pattern.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1_calculating_the_last_10_recipes() {
+        assert_eq!(
+            calculate_recipes(&mut vec![3, 7], 9, 10),
+            vec![5, 1, 5, 8, 9, 1, 6, 7, 7, 9]
+        );
+        assert_eq!(
+            calculate_recipes(&mut vec![3, 7], 5, 10),
+            vec![0, 1, 2, 4, 5, 1, 5, 8, 9, 1]
+        );
+        assert_eq!(
+            calculate_recipes(&mut vec![3, 7], 18, 10),
+            vec![9, 2, 5, 1, 0, 7, 1, 0, 8, 5]
+        );
+
+        assert_eq!(
+            calculate_recipes(&mut vec![3, 7], 2018, 10),
+            vec![5, 9, 4, 1, 4, 2, 9, 8, 8, 2]
+        );
+    }
+
+    #[test]
+    fn part_2_find_first_occurence() {
+        assert_eq!(first_occurrence(&mut vec![3, 7], vec![5, 1, 5, 8, 9]), 9);
+        assert_eq!(first_occurrence(&mut vec![3, 7], vec![0, 1, 2, 4, 5]), 5);
+        assert_eq!(first_occurrence(&mut vec![3, 7], vec![9, 2, 5, 1, 0]), 18);
+        assert_eq!(first_occurrence(&mut vec![3, 7], vec![5, 9, 4, 1, 4]), 2018);
+    }
+}
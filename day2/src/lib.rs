@@ -0,0 +1,693 @@
+use common::parse::ParseError;
+use common::{Answer, Solution};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Checks a single ID, given the number of characters all IDs must have.
+fn check_id(line: &str, expected_len: usize) -> Result<(), ParseError> {
+    if line.is_empty() {
+        return Err(ParseError::mismatch(line, "a box ID"));
+    }
+
+    if let Some((start, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(ParseError::new(
+            line,
+            start,
+            &c.to_string(),
+            "a lowercase letter",
+        ));
+    }
+
+    // Every character is ASCII by now, so lengths and offsets are the same.
+    let expected = format!("an ID of {} letters, like on line 1", expected_len);
+    if line.len() > expected_len {
+        return Err(ParseError::new(
+            line,
+            expected_len,
+            &line[expected_len..],
+            &expected,
+        ));
+    }
+    if line.len() < expected_len {
+        return Err(ParseError::new(line, line.len(), "", &expected));
+    }
+    Ok(())
+}
+
+/// Finds every line that isn't a box ID made of lowercase letters as long as
+/// the one on the first line.
+pub fn validate_ids(input: &str) -> Vec<ParseError> {
+    let expected_len = input.lines().next().map_or(0, str::len);
+
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| check_id(line, expected_len).err().map(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+pub fn parse_ids(input: &str) -> Result<Vec<String>, ParseError> {
+    match validate_ids(input).into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(input.lines().map(String::from).collect()),
+    }
+}
+
+pub fn character_frequencies(word: &str) -> HashMap<char, u32> {
+    word.chars().fold(HashMap::new(), |mut acc, c| {
+        *acc.entry(c).or_default() += 1;
+        acc
+    })
+}
+
+pub fn byte_frequencies(word: &[u8]) -> HashMap<u8, u32> {
+    word.iter().fold(HashMap::new(), |mut acc, b| {
+        *acc.entry(*b).or_default() += 1;
+        acc
+    })
+}
+
+/// Counts `a` to `z` in a fixed table instead of a `HashMap`. `None` if
+/// there's anything else in the word, or it's too long for the counts to
+/// fit into a `u8`.
+pub fn lowercase_frequencies(word: &[u8]) -> Option<[u8; 26]> {
+    if word.len() > u8::MAX as usize {
+        return None;
+    }
+
+    let mut table = [0; 26];
+    for b in word {
+        if !b.is_ascii_lowercase() {
+            return None;
+        }
+        table[(b - b'a') as usize] += 1;
+    }
+    Some(table)
+}
+
+/// For every multiplicity, how many words have a character that appears
+/// exactly that many times in them.
+#[derive(Debug, PartialEq)]
+pub struct Checksum {
+    pub counts: BTreeMap<u32, u32>,
+}
+
+impl Checksum {
+    fn new(multiplicities: &[u32]) -> Checksum {
+        Checksum {
+            counts: multiplicities.iter().map(|&m| (m, 0)).collect(),
+        }
+    }
+
+    // Counts a word once for every multiplicity among its frequencies.
+    fn add<I: Iterator<Item = u32> + Clone>(&mut self, frequencies: I) {
+        for (multiplicity, count) in self.counts.iter_mut() {
+            if frequencies.clone().any(|f| f == *multiplicity) {
+                *count += 1;
+            }
+        }
+    }
+
+    fn add_table(&mut self, table: &[u8; 26]) {
+        self.add(table.iter().filter(|&&f| f > 0).map(|&f| u32::from(f)));
+    }
+
+    pub fn count(&self, multiplicity: u32) -> u32 {
+        self.counts.get(&multiplicity).cloned().unwrap_or(0)
+    }
+
+    /// The counts of all multiplicities multiplied, the puzzle's checksum
+    /// for the multiplicities 2 and 3.
+    pub fn product(&self) -> u64 {
+        self.counts
+            .values()
+            .map(|&count| u64::from(count))
+            .product()
+    }
+}
+
+/// The checksum over the characters of each word.
+pub fn checksum(words: &[&str], multiplicities: &[u32]) -> Checksum {
+    let mut checksum = Checksum::new(multiplicities);
+    for word in words {
+        match lowercase_frequencies(word.as_bytes()) {
+            Some(table) => checksum.add_table(&table),
+            None => checksum.add(character_frequencies(word).values().cloned()),
+        }
+    }
+    checksum
+}
+
+/// The checksum over the bytes of each word, which differs from `checksum`
+/// for words with characters outside of ASCII.
+pub fn checksum_bytes(words: &[&[u8]], multiplicities: &[u32]) -> Checksum {
+    let mut checksum = Checksum::new(multiplicities);
+    for word in words {
+        match lowercase_frequencies(word) {
+            Some(table) => checksum.add_table(&table),
+            None => checksum.add(byte_frequencies(word).values().cloned()),
+        }
+    }
+    checksum
+}
+
+pub fn count_two_and_three_char_frequencies(words: &[&str]) -> (u32, u32) {
+    let checksum = checksum(words, &[2, 3]);
+    (checksum.count(2), checksum.count(3))
+}
+
+/// The number of positions at which `a` and `b` differ. If one is longer,
+/// every character past the end of the other one counts as different.
+pub fn count_different_chars(a: &str, b: &str) -> u32 {
+    let mut a_chars = a.chars();
+    let mut b_chars = b.chars();
+    let mut different = 0;
+
+    loop {
+        match (a_chars.next(), b_chars.next()) {
+            (None, None) => return different,
+            (Some(a_char), Some(b_char)) if a_char == b_char => {}
+            _ => different += 1,
+        }
+    }
+}
+
+/// The number of characters to insert, delete or substitute to turn `a`
+/// into `b`.
+pub fn levenshtein_distance(a: &str, b: &str) -> u32 {
+    let b: Vec<char> = b.chars().collect();
+    // The distances from the first characters of `a` seen so far to every
+    // prefix of `b`.
+    let mut previous: Vec<u32> = (0..=b.len() as u32).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i as u32 + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distance {
+    Hamming,
+    Levenshtein,
+}
+
+impl Distance {
+    pub fn between(self, a: &str, b: &str) -> u32 {
+        match self {
+            Distance::Hamming => count_different_chars(a, b),
+            Distance::Levenshtein => levenshtein_distance(a, b),
+        }
+    }
+}
+
+/// IDs that are connected by chains of IDs at most the threshold apart.
+#[derive(Debug, PartialEq)]
+pub struct Cluster<'a> {
+    /// The ID with the smallest total distance to all others, the first one
+    /// of those if there are several.
+    pub representative: &'a str,
+    /// In the order of the input.
+    pub members: Vec<&'a str>,
+}
+
+impl<'a> Cluster<'a> {
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+fn find_root(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    // Point everything on the way directly at the root for the next time.
+    let mut i = i;
+    while parents[i] != root {
+        let next = parents[i];
+        parents[i] = root;
+        i = next;
+    }
+    root
+}
+
+/// Groups `words` so that every word is at most `threshold` apart from at
+/// least one other word of its cluster. Biggest clusters first.
+pub fn cluster<'a>(words: &[&'a str], distance: Distance, threshold: u32) -> Vec<Cluster<'a>> {
+    let lengths: Vec<u32> = words.iter().map(|w| w.chars().count() as u32).collect();
+    let mut parents: Vec<usize> = (0..words.len()).collect();
+
+    for i in 0..words.len() {
+        for j in i + 1..words.len() {
+            // Both distances are at least the difference in length, which
+            // is a lot cheaper to check.
+            if lengths[i].max(lengths[j]) - lengths[i].min(lengths[j]) > threshold {
+                continue;
+            }
+            if distance.between(words[i], words[j]) <= threshold {
+                let (a, b) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut members: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for (i, word) in words.iter().enumerate() {
+        let root = find_root(&mut parents, i);
+        members.entry(root).or_default().push(word);
+    }
+
+    let mut clusters: Vec<Cluster> = members
+        .into_values()
+        .map(|members| {
+            let total_distance =
+                |a: &str| -> u32 { members.iter().map(|b| distance.between(a, b)).sum() };
+            let representative = *members.iter().min_by_key(|a| total_distance(a)).unwrap();
+            Cluster {
+                representative,
+                members,
+            }
+        })
+        .collect();
+
+    // Stable, so clusters of the same size stay in the order of the input.
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.size()));
+    clusters
+}
+
+// Two words share a key if they are the same except for the character at
+// the same position, which makes them exactly one apart if those characters
+// differ.
+fn pairs_differing_by_one(words: &[&str]) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<(&str, &str), Vec<(usize, char)>> = HashMap::new();
+    for (i, word) in words.iter().enumerate() {
+        for (start, c) in word.char_indices() {
+            let key = (&word[..start], &word[start + c.len_utf8()..]);
+            buckets.entry(key).or_default().push((i, c));
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for bucket in buckets.values() {
+        for (n, &(i, a)) in bucket.iter().enumerate() {
+            for &(j, b) in &bucket[n + 1..] {
+                if a != b {
+                    pairs.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+// Split into `k + 1` blocks, two words that are at most `k` apart have at
+// least one block in common. Only words sharing a block are compared.
+fn pairs_differing_by_k(words: &[&str], k: usize) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<(usize, usize, &str), Vec<usize>> = HashMap::new();
+    for (i, word) in words.iter().enumerate() {
+        let offsets: Vec<usize> = word
+            .char_indices()
+            .map(|(start, _)| start)
+            .chain(Some(word.len()))
+            .collect();
+        let len = offsets.len() - 1;
+
+        for block in 0..=k {
+            let start = offsets[block * len / (k + 1)];
+            let end = offsets[(block + 1) * len / (k + 1)];
+            // Words of different lengths are never compared.
+            let key = (len, block, &word[start..end]);
+            buckets.entry(key).or_default().push(i);
+        }
+    }
+
+    let mut candidates = HashSet::new();
+    for bucket in buckets.values() {
+        for (n, &i) in bucket.iter().enumerate() {
+            for &j in &bucket[n + 1..] {
+                if i != j {
+                    candidates.insert((i.min(j), i.max(j)));
+                }
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter(|&(i, j)| count_different_chars(words[i], words[j]) as usize == k)
+        .collect()
+}
+
+/// All pairs of words, by index and with the smaller index first, that have
+/// the same length and differ in exactly `k` positions. Sorted, so the first
+/// pair is the one a scan through the words would find first.
+pub fn pairs_differing_by(words: &[&str], k: usize) -> Vec<(usize, usize)> {
+    let mut pairs = if k == 1 {
+        pairs_differing_by_one(words)
+    } else {
+        pairs_differing_by_k(words, k)
+    };
+    pairs.sort();
+    pairs
+}
+
+pub fn differ_by<'a>(words: &[&'a str], num: u32) -> Option<(&'a str, &'a str)> {
+    pairs_differing_by(words, num as usize)
+        .first()
+        .map(|&(i, j)| (words[i], words[j]))
+}
+
+/// Two IDs that differ in a given number of positions.
+#[derive(Debug, PartialEq)]
+pub struct Match<'a> {
+    pub a: &'a str,
+    pub b: &'a str,
+    /// The positions, in characters, at which `a` and `b` differ.
+    pub differences: Vec<usize>,
+    /// The characters `a` and `b` have in common, in order.
+    pub common: String,
+}
+
+impl<'a> Match<'a> {
+    pub fn new(a: &'a str, b: &'a str) -> Match<'a> {
+        let mut differences = Vec::new();
+        let mut common = String::new();
+
+        for (i, (a_char, b_char)) in a.chars().zip(b.chars()).enumerate() {
+            if a_char == b_char {
+                common.push(a_char);
+            } else {
+                differences.push(i);
+            }
+        }
+
+        Match {
+            a,
+            b,
+            differences,
+            common,
+        }
+    }
+}
+
+/// Every pair of words that differ in exactly `num` positions, in the order
+/// they appear in `words`. A word is never paired with itself.
+pub fn find_matches<'a>(words: &[&'a str], num: u32) -> Vec<Match<'a>> {
+    pairs_differing_by(words, num as usize)
+        .into_iter()
+        .map(|(i, j)| Match::new(words[i], words[j]))
+        .collect()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        parse_ids(input)
+    }
+
+    fn part_1(input: &Vec<String>) -> Answer {
+        let words: Vec<&str> = input.iter().map(String::as_str).collect();
+        let checksum = checksum(&words, &[2, 3]);
+        Answer::new(checksum.product())
+            .with("two", checksum.count(2))
+            .with("three", checksum.count(3))
+    }
+
+    fn part_2(input: &Vec<String>) -> Answer {
+        let words: Vec<&str> = input.iter().map(String::as_str).collect();
+        match find_matches(&words, 1).first() {
+            None => Answer::new("nothing found!"),
+            Some(m) => Answer::new(&m.common)
+                .with("ids", vec![m.a, m.b])
+                .with("differences", m.differences.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn getting_character_frequencies() {
+        let input = "abbcccdddd";
+
+        let mut expected = HashMap::new();
+        expected.insert('a', 1);
+        expected.insert('b', 2);
+        expected.insert('c', 3);
+        expected.insert('d', 4);
+
+        let frequencies = character_frequencies(input);
+
+        assert_eq!(frequencies, expected);
+    }
+
+    #[test]
+    fn get_num_words_with_two_and_three_char_frequencies() {
+        let input = &["aaXXXXXX", "bXXXXXXX", "cccXXXXX", "dddXXXXX"];
+
+        let (two, three) = count_two_and_three_char_frequencies(input);
+        assert_eq!(two, 1);
+        assert_eq!(three, 2);
+    }
+
+    #[test]
+    fn get_num_different_chars() {
+        assert_eq!(count_different_chars("abcde", "axcye"), 2);
+        assert_eq!(count_different_chars("fghij", "fguij"), 1);
+    }
+
+    #[test]
+    fn find_words_with_one_different_char() {
+        let input = &[
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+
+        assert_eq!(differ_by(input, 1).unwrap(), ("fghij", "fguij"));
+        assert_eq!(differ_by(input, 2).unwrap(), ("abcde", "axcye"));
+    }
+
+    #[test]
+    fn validating_ids() {
+        assert!(validate_ids("abcde\nfghij\nklmno").is_empty());
+        assert_eq!(parse_ids("abcde\nfghij").unwrap(), vec!["abcde", "fghij"]);
+
+        let errors = validate_ids("abcde\nfgh\nklmno\npqRst\nuvwxyzz\n\nabcde");
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 4, 5, 6]);
+
+        assert_eq!(errors[0].column, 4);
+        assert_eq!(errors[0].found, "");
+        assert_eq!(errors[0].expected, "an ID of 5 letters, like on line 1");
+        assert_eq!(errors[1].column, 3);
+        assert_eq!(errors[1].found, "R");
+        assert_eq!(errors[2].column, 6);
+        assert_eq!(errors[2].found, "zz");
+
+        assert_eq!(parse_ids("abcde\nfgh\nkl").unwrap_err().line, 2);
+    }
+
+    // Compares every word with every other word, like `differ_by` used to.
+    fn brute_force_pairs(words: &[&str], k: usize) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..words.len() {
+            for j in i + 1..words.len() {
+                let same_length = words[i].chars().count() == words[j].chars().count();
+                if same_length && count_different_chars(words[i], words[j]) as usize == k {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn finding_all_pairs() {
+        let input = &["abcde", "abcdf", "abcde", "xbcdf", "abcd", "äbcde", "zbcdf"];
+
+        assert_eq!(
+            pairs_differing_by(input, 1),
+            vec![(0, 1), (0, 5), (1, 2), (1, 3), (1, 6), (2, 5), (3, 6)]
+        );
+        assert_eq!(pairs_differing_by(input, 0), vec![(0, 2)]);
+        assert_eq!(
+            pairs_differing_by(input, 2),
+            vec![(0, 3), (0, 6), (1, 5), (2, 3), (2, 6), (3, 5), (5, 6)]
+        );
+        assert_eq!(pairs_differing_by(input, 6), vec![]);
+    }
+
+    #[test]
+    fn indexed_search_finds_what_brute_force_finds() {
+        // Short words over three letters, so that there are lots of pairs.
+        let mut seed: u32 = 2018;
+        let words: Vec<String> = (0..300)
+            .map(|_| {
+                (0..6)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                        (b'a' + (seed >> 16) as u8 % 3) as char
+                    })
+                    .collect()
+            })
+            .collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        for k in 0..=6 {
+            assert_eq!(pairs_differing_by(&words, k), brute_force_pairs(&words, k));
+        }
+    }
+
+    #[test]
+    fn finding_matches() {
+        let input = &[
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+
+        let matches = find_matches(input, 1);
+        assert_eq!(
+            matches,
+            vec![Match {
+                a: "fghij",
+                b: "fguij",
+                differences: vec![2],
+                common: String::from("fgij"),
+            }]
+        );
+
+        let matches = find_matches(input, 2);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].differences, vec![1, 3]);
+        assert_eq!(matches[0].common, "ace");
+
+        // A word is never its own match, only a duplicate of it is.
+        assert_eq!(find_matches(input, 0), vec![]);
+        assert_eq!(differ_by(input, 0), None);
+        let matches = find_matches(&["abc", "xyz", "abc"], 0);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].common, "abc");
+    }
+
+    #[test]
+    fn checksums_over_any_multiplicities() {
+        let input = &[
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ];
+
+        let sum = checksum(input, &[2, 3]);
+        assert_eq!(sum.count(2), 4);
+        assert_eq!(sum.count(3), 3);
+        assert_eq!(sum.product(), 12);
+
+        let sum = checksum(input, &[1, 2, 3, 4]);
+        assert_eq!(sum.count(1), 6);
+        assert_eq!(sum.count(4), 0);
+        assert_eq!(sum.product(), 0);
+        assert_eq!(checksum(input, &[0]).count(0), 0);
+
+        let bytes: Vec<&[u8]> = input.iter().map(|word| word.as_bytes()).collect();
+        assert_eq!(checksum_bytes(&bytes, &[1, 2, 3, 4]), sum);
+    }
+
+    #[test]
+    fn checksums_over_chars_and_bytes() {
+        // `ä` is two bytes in UTF-8, both of which appear twice.
+        let input = &["xää", "xxä", "ÄÄÄ"];
+        let sum = checksum(input, &[1, 2, 3, 4]);
+        assert_eq!(
+            sum.counts.values().collect::<Vec<_>>(),
+            vec![&2, &2, &1, &0]
+        );
+
+        let bytes: Vec<&[u8]> = input.iter().map(|word| word.as_bytes()).collect();
+        let sum = checksum_bytes(&bytes, &[1, 2, 3, 4]);
+        assert_eq!(
+            sum.counts.values().collect::<Vec<_>>(),
+            vec![&2, &2, &1, &0]
+        );
+        let sum = checksum_bytes(&bytes[..1], &[1, 2, 3, 4]);
+        assert_eq!(
+            sum.counts.values().collect::<Vec<_>>(),
+            vec![&1, &1, &0, &0]
+        );
+
+        // `ä` and `ö` start with the same byte, so "äö" has a byte twice but
+        // no character twice. A combining accent is a character of its own.
+        let input = &["äö", "éé", "e\u{301}"];
+        let chars = checksum(input, &[1, 2, 3]);
+        assert_eq!(chars.counts.values().collect::<Vec<_>>(), vec![&2, &1, &0]);
+
+        let bytes: Vec<&[u8]> = input.iter().map(|word| word.as_bytes()).collect();
+        let bytes = checksum_bytes(&bytes, &[1, 2, 3]);
+        assert_eq!(bytes.counts.values().collect::<Vec<_>>(), vec![&2, &2, &0]);
+        assert_ne!(chars, bytes);
+    }
+
+    #[test]
+    fn counting_lowercase_in_a_table() {
+        let table = lowercase_frequencies(b"abbcccz").unwrap();
+        assert_eq!(&table[..3], &[1, 2, 3]);
+        assert_eq!(table[25], 1);
+
+        assert_eq!(lowercase_frequencies(b"abC"), None);
+        assert_eq!(lowercase_frequencies("ä".as_bytes()), None);
+        assert_eq!(lowercase_frequencies(&[b'a'; 256]), None);
+        assert!(lowercase_frequencies(&[b'a'; 255]).is_some());
+    }
+
+    #[test]
+    fn counting_different_chars_of_unequal_length() {
+        assert_eq!(count_different_chars("abc", "abcde"), 2);
+        assert_eq!(count_different_chars("abcde", "abc"), 2);
+        assert_eq!(count_different_chars("äbc", "abc"), 1);
+        assert_eq!(count_different_chars("", "ab"), 2);
+    }
+
+    #[test]
+    fn measuring_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("abcde", "abde"), 1);
+        assert_eq!(levenshtein_distance("abcde", "bcdea"), 2);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+        assert_eq!(levenshtein_distance("äbc", "abc"), 1);
+    }
+
+    #[test]
+    fn clustering_ids() {
+        let input = &[
+            "abcde", "fghij", "abcdf", "fguij", "abcdx", "xyz", "bcdex", "abxdx",
+        ];
+
+        let clusters = cluster(input, Distance::Hamming, 1);
+        let sizes: Vec<usize> = clusters.iter().map(Cluster::size).collect();
+        assert_eq!(sizes, vec![4, 2, 1, 1]);
+        assert_eq!(
+            clusters[0],
+            Cluster {
+                representative: "abcdx",
+                members: vec!["abcde", "abcdf", "abcdx", "abxdx"],
+            }
+        );
+        assert_eq!(clusters[1].members, vec!["fghij", "fguij"]);
+        assert_eq!(clusters[1].representative, "fghij");
+
+        // Shifted by one, `bcdex` is far from `abcde` by Hamming distance
+        // but close by Levenshtein.
+        let clusters = cluster(input, Distance::Levenshtein, 2);
+        let sizes: Vec<usize> = clusters.iter().map(Cluster::size).collect();
+        assert_eq!(sizes, vec![5, 2, 1]);
+        assert!(clusters[0].members.contains(&"bcdex"));
+
+        assert_eq!(cluster(&[], Distance::Hamming, 1), vec![]);
+    }
+}
@@ -1,366 +1,100 @@
-use std::collections::HashMap;
-
-const PART_1_INPUT: &'static [&str] = &[
-    "rvefnvyxzbodgpnpkumawhijsc",
-    "rvefqtyxzsddglnppumawhijsc",
-    "rvefqtywzbodglnkkubawhijsc",
-    "rvefqpyxzbozglnpkumawhiqsc",
-    "rvefqtyxzbotgenpkuyawhijsc",
-    "rvefqtyxzbodglnlkumtphijsc",
-    "rwefqtykzbodglnpkumawhijss",
-    "rvynqtyxzbodglnpkumawrijsc",
-    "rvefqtyxlbodgcnpkumawhijec",
-    "rvefqtyxzbodmlnpnumawhijsx",
-    "rvefqtyxzbqdbdnpkumawhijsc",
-    "rvefqtyxzlodblnpkuiawhijsc",
-    "rvefqtyizrodelnpkumawhijsc",
-    "rveffjyxzgodglnpkumawhijsc",
-    "rvefqjyxzbodalnpkumadhijsc",
-    "rvefqtidzbodglnpkumawhdjsc",
-    "hvefqtygzbodglnpkumawhijfc",
-    "rzefqtyxzbodglfhkumawhijsc",
-    "rmefqtyxzbolglnpkumaehijsc",
-    "rnefqqyxzbodglnhkumawhijsc",
-    "rvwfqvyxzbodglnpcumawhijsc",
-    "rvefqtyxzbokgltpkumavhijsc",
-    "rvefciyxzbodglnmkumawhijsc",
-    "rvefptyxzbodglnpkuhashijsc",
-    "rvefqtyxzrodglnpkxmawhiqsc",
-    "rvefqtyxzbotglnpkumawriwsc",
-    "rvufqtyxzbodglnplumawhijvc",
-    "rvefutykzbodglnpkumaahijsc",
-    "rvefqtyxqbodgllprumawhijsc",
-    "rvegqttxzbodgllpkumawhijsc",
-    "dvefqtyxzsodglnpkumawdijsc",
-    "rvefqtyxkbodglnfkumawhijsj",
-    "rvefqtyxzbodnlnpcumawhijnc",
-    "rvefqtyxzbodglfpkuocwhijsc",
-    "rvecqtyxzbbdganpkumawhijsc",
-    "rvefytyxzbodglnpkubgwhijsc",
-    "rvefxtyazbomglnpkumawhijsc",
-    "rvefqgyxzbodglnpkumawyiksc",
-    "avefqtyxzbodglnfkummwhijsc",
-    "fvefqtyxzbbdglnpkumswhijsc",
-    "rvefqtyxzxodglnpkumuuhijsc",
-    "rvezqtyxzbydclnpkumawhijsc",
-    "rvefqtyxzbohglnpkumawdijjc",
-    "rvejqtyxzbodrlnpkumawhijsd",
-    "rvefitzxzbxdglnpkumawhijsc",
-    "rvefutyxzbvdglnikumawhijsc",
-    "rvefqtyazbodgqnbkumawhijsc",
-    "rvefqtyxzbolglnpkwmajhijsc",
-    "rvefqtyxzjodglnpgwmawhijsc",
-    "rvefhtyxzbodglbpaumawhijsc",
-    "mvexqtyxzbodglnpkumawrijsc",
-    "rvefqtyxwbodglnpkumawhbxsc",
-    "rvefqtyxzbodgsnpkudawsijsc",
-    "rvwfqtyxzbonglnwkumawhijsc",
-    "rvefqtyxzjodglnpkfmawhwjsc",
-    "rvefqtyxzbodglntkumughijsc",
-    "rvefctyxzbodglnpkumawhiwsx",
-    "avefqtyvzbodglnpkumawhijsb",
-    "rfefqtyxzlodglnphumawhijsc",
-    "rvefqtyxzfowglnpkumaehijsc",
-    "rvhfvtyxzbodgqnpkumawhijsc",
-    "rfefqtyxzbodglapkumuwhijsc",
-    "rvefqclxzbodglnzkumawhijsc",
-    "qvefqtyxzbodglnckumcwhijsc",
-    "rvefqtyxzkodglnpkymawgijsc",
-    "rvefqtyxzbodgfnpkumafhizsc",
-    "rvefqtyxzbodglnxkumavhijsf",
-    "rvevqtyxzbodgpnpkurawhijsc",
-    "rvefqtyxziodglnpkubawhijss",
-    "rrefqtpxzyodglnpkumawhijsc",
-    "rvefqfyxzbodglcpkxmawhijsc",
-    "rvefdtyxzbodglnpkumvwhijsn",
-    "rverqtyxzbodglnpkwmawhijuc",
-    "rvecjtyxzboxglnpkumawhijsc",
-    "rvefqtyxzbodglnpkqmaxhifsc",
-    "rtnfqtyxzbodglnpkumawhijmc",
-    "lvefqtyxzbodelnpkumawhijsz",
-    "dvefqtyxzbbdgvnpkumawhijsc",
-    "rvefqlyhzbodglnpkumtwhijsc",
-    "roefqtyxlbodglnpkumawhyjsc",
-    "rvefqsydzjodglnpkumawhijsc",
-    "rveybtyxzbodglnpkumawhijsn",
-    "rvefqtyhzbodgvnpmumawhijsc",
-    "rvefqxyazboddlnpkumawhijsc",
-    "vvefqtyxzbohglqpkumawhijsc",
-    "reefhtyxzbodglnpkkmawhijsc",
-    "rvefqtyxzbodglnpkulowhijrc",
-    "rveqqtyxzbodgknpkumawhijsk",
-    "jvefqtqxzbodglnpkumawiijsc",
-    "rvefqtyxzboxglnpvuqawhijsc",
-    "rvefquyxzbodglwwkumawhijsc",
-    "rvefqtyxzbodnlnpkumawhgjbc",
-    "rvdfqthxdbodglnpkumawhijsc",
-    "rvefqtyxzbodllnpkumawhujsb",
-    "evefqtyxzboyglnpkumowhijsc",
-    "rvefktyxzbomglnpzumawhijsc",
-    "rvefqtyxzbodhlnnkrmawhijsc",
-    "rvefqtyxrbodglnpkujaehijsc",
-    "rvefqtyzzbodglnpkumrwhijsb",
-    "evefqtyxzpodglfpkumawhijsc",
-    "rvefqtyxibodglkpyumawhijsc",
-    "rrefqtyxzbodglnpkudawhajsc",
-    "rvifqtyxzbodglxpkumawhijlc",
-    "rxefqtyxzbedglnpkumawhijsp",
-    "rvnfqtyxzbopglnpkuqawhijsc",
-    "rvefqtyxkbodglnpoumawoijsc",
-    "dvefwtyxzbodglnpksmawhijsc",
-    "rvkfqtyxzbodglnpkdmawhijsa",
-    "rcefytyxzzodglnpkumawhijsc",
-    "rvefqtkxzbodglnpktqawhijsc",
-    "nvezqhyxzbodglnpkumawhijsc",
-    "rrefqtyxzbodgunpkumpwhijsc",
-    "rvefqtaxzbodgknpkumawhijic",
-    "pvefqtyxzbodglnpkuxawsijsc",
-    "rvefqtyxzbodglkpvumawhjjsc",
-    "wvefqtyxzkodglnpkumawhhjsc",
-    "rzefqtyxzbotglnpkumawhxjsc",
-    "rvefqtxpzbodglnpkumawzijsc",
-    "bgefqtyxzbodglnpkrmawhijsc",
-    "rvefqlyxzbodglnpkumilhijsc",
-    "cbefqtyxzbodglnpkumawhiesc",
-    "rvefqtyxzbydelnpkumahhijsc",
-    "rvefntyxzbodglnpkumaehijsw",
-    "rverqtyxztodglopkumawhijsc",
-    "rvefqtyxzdodgwrpkumawhijsc",
-    "rvefqtyxibodglnikumawhtjsc",
-    "qvafqtyxzbodglnpkurawhijsc",
-    "rvefqtyxwbodglnpaumawoijsc",
-    "rvefqtyxzoodglndknmawhijsc",
-    "rvdfqtlxzyodglnpkumawhijsc",
-    "rvefqtyxzbodglngfumawhinsc",
-    "rsefqtyxzbodglnpkumawhijek",
-    "rvoestyxzbodglnpkumawhijsc",
-    "svefqtyxzboaglnprumawhijsc",
-    "rvefqtybzbodgwnpkumawwijsc",
-    "rvefqtyxzdwdglnpkvmawhijsc",
-    "rvlfqtyxzbodglnpkrmawhixsc",
-    "rvefqtyxwbodglepkumawhijsd",
-    "rvefqtbxzbodglnqkumawhijmc",
-    "rvefqtzxzbodglnpkumuzhijsc",
-    "rvefqtyxzbodglnpkumawzwnsc",
-    "rvwfqtyxzboiglnpkumawhijsg",
-    "rtehotyxzbodglnpkudawhijsc",
-    "rvegqtyxzbodglnpyumawhijsl",
-    "rvecqtyxzbsdglnpkumawhojsc",
-    "rvefqtyxzbodmlnpkumaghijfc",
-    "rvefqtyxzxodglnpkumanvijsc",
-    "rvefqtyxzbodglnbiugawhijsc",
-    "lvefqtlxzbodglnplumawhijsc",
-    "rvefqtyxvbodglnpkumaldijsc",
-    "rmefqtyxzbodgvnpkuuawhijsc",
-    "rvefqtyxzbodglnpkymeuhijsc",
-    "rvefqtyxzuodganpsumawhijsc",
-    "rxefqtyxzbodglnpkumgwhijwc",
-    "rvefgtyxzbodglnpkudawxijsc",
-    "ahefqtyxzbodglnpkumawhejsc",
-    "rfefqtyxzbzdglnpkusawhijsc",
-    "rvefqtyszqodgljpkumawhijsc",
-    "rvefqtylzboiglnpkumrwhijsc",
-    "rvefqtyxzltdglnpkumawhijsu",
-    "rbefqtyxzbodglnpqumawhijsi",
-    "rvefqtyozpodglnpkumawhijsa",
-    "zvefqtyxzpopglnpkumawhijsc",
-    "rvefqtyxzbodglnfkqmawhijsp",
-    "rvefqtyxzbodgliakumawhijsf",
-    "rvefqtymzrodgfnpkumawhijsc",
-    "ivejqtyxzbodglnpkumawhijuc",
-    "rvefqtyxzbodflnpkxwawhijsc",
-    "dvrfqtyxzbodglnpkumashijsc",
-    "rqefqtyxzbwdglnpkumawvijsc",
-    "tvefqtkxzbodgltpkumawhijsc",
-    "rvefdtyxzbodguxpkumawhijsc",
-    "rveqqtyxvbodglnykumawhijsc",
-    "rvefqtypzcovglnpkumawhijsc",
-    "rvefqnyxzbosglnpkumdwhijsc",
-    "rvefstjxzbodslnpkumawhijsc",
-    "rvefqzyxzpodglnpkummwhijsc",
-    "rvefqkyxzbodglnhgumawhijsc",
-    "rvufqvyxzbodklnpkumawhijsc",
-    "rvefotyxzhodglnpkumawhijsk",
-    "rvefqtyxzbokglnpkumawvcjsc",
-    "lvefqtyxzbolglnpkumawoijsc",
-    "rvefqtywzoodglfpkumawhijsc",
-    "rvehqtqxzbodglnpkumawhcjsc",
-    "rqefqtyxzbodolnpkumjwhijsc",
-    "rvefqtyxzbodglrpkunawgijsc",
-    "rvefqtyxzbodglamkumawdijsc",
-    "rvefvtyzzbodllnpkumawhijsc",
-    "rvefqtyxzbldglnpfcmawhijsc",
-    "rvefppyxzbodglnpkucawhijsc",
-    "rvefquyuzbodglnpkumkwhijsc",
-    "rvefqtyxzbodgqxpkumawhivsc",
-    "rtefotyxzbodglnpkudawhijsc",
-    "rvefqtyxzbodgbnmkuzawhijsc",
-    "ivefqtyxzbodgsnpkumzwhijsc",
-    "rvhfqtyxzbodolnpkumawhijsz",
-    "rvefvtyxzbodwlnpkusawhijsc",
-    "riemqtyxzbodglnpkumawhiasc",
-    "rvtfqtyxzbqdglnpkumawuijsc",
-    "raesqtyxzbodglnpkumawhijsj",
-    "rvefqtyxzbodalmpkumawhihsc",
-    "rvefqtlxzbodgznpkkmawhijsc",
-    "rvefqbyxzbodglgpkubawhijsc",
-    "rvefqtyxnbodgxnpkumswhijsc",
-    "rvefqtyxzkodvlnukumawhijsc",
-    "rvefqtyzzbocglnpkumafhijsc",
-    "rvhfqtyxzbodglmpkumgwhijsc",
-    "rvsfrtyxzbodnlnpkumawhijsc",
-    "rvefqtyxzbxdglnpkujcwhijsc",
-    "rvefqtyvzrodglnphumawhijsc",
-    "reetatyxzbodglnpkumawhijsc",
-    "rvefqtyxzbodglnpzumaoqijsc",
-    "ovefqtyyzbodglnvkumawhijsc",
-    "rvefqbyxzbodnlnpkumawhijsi",
-    "xvefqtyxzbodgrnpkumawrijsc",
-    "rvebqtyxzbodglnpkumazhiasc",
-    "rqeretyxzbodglnpkumawhijsc",
-    "rvefqtyxzyodglapkumvwhijsc",
-    "rvesqxyxzbodglnpvumawhijsc",
-    "rvefqtyxeborglnpkufawhijsc",
-    "rvecqtyxzbodflnpkumawnijsc",
-    "rvefdpyxtbodglnpkumawhijsc",
-    "rvefqtyfzbodclnpkymawhijsc",
-    "rvefqtywzbodglnpxumawhiusc",
-    "rvefqtyxzbodglnpkumawzbjwc",
-    "rvewqtyxdbodglnpxumawhijsc",
-    "rvefqtyxzgocglnpkgmawhijsc",
-    "rvufqtyxzbodggnpkuzawhijsc",
-    "rvefqtynzlodgllpkumawhijsc",
-    "rvedqtyxzbodghnpkumawhujsc",
-    "rvefqtyxlbodgnnpkpmawhijsc",
-    "rvefqtyxzboqglnpkzmawhijec",
-    "rvefqtyxzbodglnpkfmwwyijsc",
-    "rwefqtkxzbodzlnpkumawhijsc",
-    "rvefqtyxvbodglnpkufawhyjsc",
-    "rvefqtyxzbodgltpkumawhqmsc",
-    "rvefctyxzbodglfpkumathijsc",
-    "rvefqtyxzbodgfnpkuuawhijfc",
-    "rvefqttxzbodglnpmumawhijwc",
-    "rvefqtyxzbodglnpkqmawhihsj",
-    "rvefqtyxzbsdglcnkumawhijsc",
-    "rvbiqtyxzbodglnpkumawhijlc",
-    "rnefqtylzvodglnpkumawhijsc",
-    "mvefqtyxzbddglnpkumcwhijsc",
-    "rvefwtyxzbodglnpkgmawhijxc",
-    "rvefqtyxljodglnpkumxwhijsc",
-    "rvefqtyxzbodglnpkuprwhijsd",
-    "rcxfqtyxzbldglnpkumawhijsc",
-    "rvetqtyxzbojglnpkumewhijsc",
-    "rvxfqtyxzbtdglnpkbmawhijsc",
-];
-
-fn character_frequencies(word: &str) -> HashMap<char, u32> {
-    word.chars().fold(HashMap::new(), |mut acc, c| {
-        *acc.entry(c).or_default() += 1;
-        acc
-    })
-}
-
-fn count_two_and_three_char_frequencies(words: &[&str]) -> (u32, u32) {
-    let mut exactly_two = 0;
-    let mut exactly_three = 0;
-
-    words
-        .iter()
-        .map(|word| character_frequencies(word))
-        .for_each(|frequencies| {
-            if frequencies.values().any(|&x| x == 2) {
-                exactly_two += 1;
-            }
-
-            if frequencies.values().any(|&x| x == 3) {
-                exactly_three += 1;
-            }
-        });
-
-    (exactly_two, exactly_three)
-}
-
-fn count_different_chars(a: &str, b: &str) -> u32 {
-    let different = a.len() as u32;
-
-    a.chars().zip(b.chars()).fold(
-        different,
-        |acc, (a_char, b_char)| {
-            if a_char == b_char {
-                acc - 1
-            } else {
-                acc
-            }
-        },
-    )
-}
-
-fn differ_by<'a>(words: &[&'a str], num: u32) -> Option<(&'a str, &'a str)> {
-    for a in words {
-        for b in words {
-            if count_different_chars(a, b) == num {
-                return Some((a, b));
-            }
+use common::answers::Answers;
+use common::input;
+use common::output::{self, Format};
+use day2::*;
+use std::env;
+use std::process;
+
+// Options followed by a value.
+const OPTIONS: &[&str] = &["--cluster", "--distance"];
+
+fn print_clusters(contents: &str, threshold: &str) {
+    let threshold = threshold.parse::<u32>().unwrap_or_else(|_| {
+        eprintln!("invalid threshold `{}`, expected a number", threshold);
+        process::exit(1);
+    });
+    let distance = match input::option("--distance").as_deref() {
+        None | Some("hamming") => Distance::Hamming,
+        Some("levenshtein") => Distance::Levenshtein,
+        Some(other) => {
+            eprintln!(
+                "unknown distance `{}`, expected `hamming` or `levenshtein`",
+                other
+            );
+            process::exit(1);
         }
-    }
+    };
 
-    return None;
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn getting_character_frequencies() {
-        let input = "abbcccdddd";
-
-        let mut expected = HashMap::new();
-        expected.insert('a', 1);
-        expected.insert('b', 2);
-        expected.insert('c', 3);
-        expected.insert('d', 4);
-
-        let frequencies = character_frequencies(input);
-
-        assert_eq!(frequencies, expected);
+    let ids: Vec<&str> = contents.lines().collect();
+    let clusters = cluster(&ids, distance, threshold);
+    for cluster in &clusters {
+        println!("{:>6}  {}", cluster.size(), cluster.representative);
     }
+    println!("{} clusters of {} IDs", clusters.len(), ids.len());
+}
 
-    #[test]
-    fn get_num_words_with_two_and_three_char_frequencies() {
-        let input = &["aaXXXXXX", "bXXXXXXX", "cccXXXXX", "dddXXXXX"];
-
-        let (two, three) = count_two_and_three_char_frequencies(input);
-        assert_eq!(two, 1);
-        assert_eq!(three, 2);
+fn main() -> Result<(), std::io::Error> {
+    let record = env::args().any(|arg| arg == "--record");
+    let input = input::from_args(env!("CARGO_MANIFEST_DIR"), OPTIONS).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let contents = &input.contents;
+
+    if Format::from_args() == Format::Json {
+        output::print_json::<Day2>(2, &input);
+        return Ok(());
     }
 
-    #[test]
-    fn get_num_different_chars() {
-        assert_eq!(count_different_chars("abcde", "axcye"), 2);
-        assert_eq!(count_different_chars("fghij", "fguij"), 1);
+    // Clustering works on any IDs, even of different lengths, so it comes
+    // before validating them for the puzzle.
+    if let Some(threshold) = input::option("--cluster") {
+        print_clusters(contents, &threshold);
+        return Ok(());
     }
 
-    #[test]
-    fn find_words_with_one_different_char() {
-        let input = &[
-            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
-        ];
-
-        assert_eq!(differ_by(input, 1).unwrap(), ("fghij", "fguij"));
-        assert_eq!(differ_by(input, 2).unwrap(), ("abcde", "axcye"));
+    // Report every bad line at once, not just the first one.
+    let errors = validate_ids(contents);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}\n", error.diagnostic(&input.source));
+        }
+        eprintln!("{} invalid box ID(s)", errors.len());
+        process::exit(1);
     }
-}
+    let ids: Vec<&str> = contents.lines().collect();
 
-fn main() {
-    let (exactly_two, exactly_three) = count_two_and_three_char_frequencies(PART_1_INPUT);
+    let mut answers = Answers::load()?;
 
     // Part 1
-    println!("Part 1 - result={}", exactly_two * exactly_three);
+    let (exactly_two, exactly_three) = count_two_and_three_char_frequencies(&ids);
+    let checksum = (exactly_two * exactly_three).to_string();
+    println!(
+        "Part 1 - result={} ({})",
+        checksum,
+        answers.check(2, 1, contents, &checksum)
+    );
 
     // Part 2
-    match differ_by(PART_1_INPUT, 1) {
-        None => println!("nothing found!"),
-        Some((word_a, word_b)) => println!("word_a={}, word_b={}", word_a, word_b),
+    let matches = find_matches(&ids, 1);
+    let common = match matches.first() {
+        Some(m) => m.common.clone(),
+        None => String::from("nothing found!"),
+    };
+    println!(
+        "Part 2 - common letters={} ({})",
+        common,
+        answers.check(2, 2, contents, &common)
+    );
+    for m in &matches {
+        println!("  {} and {} differ at {:?}", m.a, m.b, m.differences);
     }
+
+    if record {
+        answers.record(2, 1, contents, &checksum);
+        answers.record(2, 2, contents, &common);
+        answers.save()?;
+    }
+
+    Ok(())
 }
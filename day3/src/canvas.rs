@@ -0,0 +1,76 @@
+use grid::{Bounds, DenseGrid, Point};
+
+/// How many claims cover each square inch of a rectangle of fabric. Drawing
+/// outside of the rectangle grows it.
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    counts: DenseGrid<u16>,
+}
+
+impl Canvas {
+    pub fn new() -> Canvas {
+        Canvas {
+            counts: DenseGrid::new(0, 0, 0),
+        }
+    }
+
+    /// A canvas that can be drawn on inside `bounds` without growing.
+    pub fn covering(bounds: Bounds) -> Canvas {
+        Canvas {
+            counts: DenseGrid::covering(bounds, 0),
+        }
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.counts.bounds()
+    }
+
+    pub fn get(&self, p: Point) -> u16 {
+        self.counts.get(p).cloned().unwrap_or(0)
+    }
+
+    pub fn insert(&mut self, p: Point, count: u16) {
+        self.counts.expand(Bounds::new(p, p), 0);
+        self.counts[p] = count;
+    }
+
+    /// Adds one to every square inch in `bounds`. Counts stop at `u16::MAX`,
+    /// which is plenty to tell overlapping inches from the others.
+    pub fn draw(&mut self, bounds: Bounds) {
+        if bounds.width() <= 0 || bounds.height() <= 0 {
+            return;
+        }
+        self.counts.expand(bounds, 0);
+        for p in bounds.points() {
+            let count = &mut self.counts[p];
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// Every square inch covered by at least one claim.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, u16)> + '_ {
+        self.counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(p, count)| (p, *count))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = u16> + '_ {
+        self.iter().map(|(_, count)| count)
+    }
+}
+
+impl Default for Canvas {
+    fn default() -> Canvas {
+        Canvas::new()
+    }
+}
+
+// Two canvases are the same if they cover the same inches equally often,
+// no matter how far each of them has grown.
+impl PartialEq for Canvas {
+    fn eq(&self, other: &Canvas) -> bool {
+        self.iter().all(|(p, count)| other.get(p) == count)
+            && other.iter().all(|(p, count)| self.get(p) == count)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Claim;
+use grid::SparseGrid;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum FabricError {
+    DuplicateId(u32),
+    /// The claim is past `i32::MAX`, so it doesn't fit on the fabric's grid.
+    OffTheGrid(u32),
+}
+
+impl fmt::Display for FabricError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FabricError::DuplicateId(id) => write!(f, "there already is a claim #{}", id),
+            FabricError::OffTheGrid(id) => write!(f, "claim #{} is too far out", id),
+        }
+    }
+}
+
+impl Error for FabricError {}
+
+// Besides how many claims cover an inch, each cell has the XOR of their
+// ids. When only one claim is left, that's its id. Inches no claim covers
+// have no cell, so claims far apart don't cost anything in between.
+#[derive(Clone, Copy, Default)]
+struct Cell {
+    count: u32,
+    ids: u32,
+}
+
+/// Claims that can be added and taken back one by one, always knowing how
+/// many square inches overlap and which claims are intact. Both take time
+/// proportional to the area of the claim.
+pub struct Fabric {
+    cells: SparseGrid<Cell>,
+    claims: BTreeMap<u32, Claim>,
+    // The number of each claim's inches that other claims cover as well.
+    shared: BTreeMap<u32, u64>,
+    intact: BTreeSet<u32>,
+    overlap: u64,
+}
+
+impl Fabric {
+    pub fn new() -> Fabric {
+        Fabric {
+            cells: SparseGrid::new(),
+            claims: BTreeMap::new(),
+            shared: BTreeMap::new(),
+            intact: BTreeSet::new(),
+            overlap: 0,
+        }
+    }
+
+    /// Square inches covered by two or more claims.
+    pub fn overlap(&self) -> u64 {
+        self.overlap
+    }
+
+    /// The claims that don't overlap with any other, by id.
+    pub fn intact(&self) -> impl Iterator<Item = u32> + '_ {
+        self.intact.iter().cloned()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Claim> {
+        self.claims.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.claims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.claims.is_empty()
+    }
+
+    // Keeps `intact` in step with the number of shared inches of claim `id`.
+    fn share(&mut self, id: u32, change: i64) {
+        let shared = self.shared.entry(id).or_default();
+        *shared = (*shared as i64 + change) as u64;
+        if *shared == 0 {
+            self.intact.insert(id);
+        } else {
+            self.intact.remove(&id);
+        }
+    }
+
+    pub fn insert(&mut self, claim: Claim) -> Result<(), FabricError> {
+        if self.claims.contains_key(&claim.id) {
+            return Err(FabricError::DuplicateId(claim.id));
+        }
+
+        let bounds = claim.bounds().ok_or(FabricError::OffTheGrid(claim.id))?;
+        self.share(claim.id, 0);
+
+        let mut shared = 0;
+        for p in bounds.points() {
+            let cell = self.cells.get(p).cloned().unwrap_or_default();
+            match cell.count {
+                0 => {}
+                // The inch was all the other claim's so far.
+                1 => {
+                    self.overlap += 1;
+                    self.share(cell.ids, 1);
+                    shared += 1;
+                }
+                _ => shared += 1,
+            }
+            self.cells.insert(
+                p,
+                Cell {
+                    count: cell.count + 1,
+                    ids: cell.ids ^ claim.id,
+                },
+            );
+        }
+
+        self.share(claim.id, shared);
+        self.claims.insert(claim.id, claim);
+        Ok(())
+    }
+
+    /// Takes back claim `id`, returning it. `None` if there is no such claim.
+    pub fn remove(&mut self, id: u32) -> Option<Claim> {
+        let claim = self.claims.remove(&id)?;
+
+        // Only claims that fit on the grid got in.
+        for p in claim.bounds().unwrap().points() {
+            let cell = *self.cells.get(p).unwrap();
+            let cell = Cell {
+                count: cell.count - 1,
+                ids: cell.ids ^ id,
+            };
+            // The inch is all the remaining claim's again.
+            if cell.count == 1 {
+                self.overlap -= 1;
+                self.share(cell.ids, -1);
+            }
+            if cell.count == 0 {
+                self.cells.remove(p);
+            } else {
+                self.cells.insert(p, cell);
+            }
+        }
+
+        self.shared.remove(&id);
+        self.intact.remove(&id);
+        Some(claim)
+    }
+}
+
+impl Default for Fabric {
+    fn default() -> Fabric {
+        Fabric::new()
+    }
+}
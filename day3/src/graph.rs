@@ -0,0 +1,101 @@
+use crate::sweep;
+use crate::Claim;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// Another claim that one overlaps with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Conflict {
+    pub id: u32,
+    /// Square inches both claims cover.
+    pub area: u64,
+}
+
+/// Which claims overlap with which, with every claim as a node and an edge
+/// between every two claims that overlap.
+#[derive(Debug)]
+pub struct OverlapGraph {
+    conflicts: BTreeMap<u32, Vec<Conflict>>,
+}
+
+impl OverlapGraph {
+    pub fn new(claims: &[Claim]) -> OverlapGraph {
+        let mut conflicts: BTreeMap<u32, Vec<Conflict>> =
+            claims.iter().map(|c| (c.id, Vec::new())).collect();
+
+        for (a, b, area) in sweep::overlapping_pairs(claims) {
+            let (a, b) = (claims[a].id, claims[b].id);
+            conflicts
+                .entry(a)
+                .or_default()
+                .push(Conflict { id: b, area });
+            conflicts
+                .entry(b)
+                .or_default()
+                .push(Conflict { id: a, area });
+        }
+        for list in conflicts.values_mut() {
+            list.sort_by_key(|c| c.id);
+        }
+
+        OverlapGraph { conflicts }
+    }
+
+    /// The claims overlapping with claim `id`, by id. `None` if there is no
+    /// such claim.
+    pub fn conflicts(&self, id: u32) -> Option<&[Conflict]> {
+        self.conflicts.get(&id).map(|list| list.as_slice())
+    }
+
+    /// Groups of claims connected through overlaps, biggest first. The ids
+    /// in each group are sorted; intact claims are groups of their own.
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        let mut seen = BTreeSet::new();
+        let mut components = Vec::new();
+
+        for &start in self.conflicts.keys() {
+            if !seen.insert(start) {
+                continue;
+            }
+
+            let mut component = vec![start];
+            let mut i = 0;
+            while i < component.len() {
+                for conflict in &self.conflicts[&component[i]] {
+                    if seen.insert(conflict.id) {
+                        component.push(conflict.id);
+                    }
+                }
+                i += 1;
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        // Stable, so groups of the same size stay ordered by their lowest id.
+        components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+        components
+    }
+
+    /// The graph in Graphviz' DOT language, with the shared area on each
+    /// edge. `dot -Tsvg` turns it into a picture.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph claims {\n");
+        for (id, conflicts) in &self.conflicts {
+            if conflicts.is_empty() {
+                writeln!(dot, "    {} [style=filled, fillcolor=palegreen];", id).unwrap();
+            }
+            for conflict in conflicts.iter().filter(|c| c.id > *id) {
+                writeln!(
+                    dot,
+                    "    {} -- {} [label=\"{}\"];",
+                    id, conflict.id, conflict.area
+                )
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,723 @@
+#[macro_use]
+extern crate lazy_static;
+
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use grid::{Bounds, Point};
+use regex::Regex;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+mod canvas;
+mod fabric;
+mod graph;
+mod render;
+mod sweep;
+
+pub use crate::canvas::Canvas;
+pub use crate::fabric::{Fabric, FabricError};
+pub use crate::graph::{Conflict, OverlapGraph};
+pub use crate::render::Image;
+
+/// What's wrong with a claim. Each kind points at where in the line it is.
+#[derive(Debug, PartialEq)]
+pub enum ClaimError {
+    /// The line doesn't look like a claim, or a number doesn't fit a u32.
+    Malformed(ParseError),
+    /// The claim is zero inches wide or high.
+    ZeroArea(ParseError),
+    /// The claim reaches beyond `u32::MAX` inches.
+    Overflow(ParseError),
+    /// An earlier line of the input has a claim with the same id.
+    DuplicateId {
+        id: u32,
+        first_line: usize,
+        error: ParseError,
+    },
+}
+
+impl ClaimError {
+    pub fn parse_error(&self) -> &ParseError {
+        match self {
+            ClaimError::Malformed(error)
+            | ClaimError::ZeroArea(error)
+            | ClaimError::Overflow(error)
+            | ClaimError::DuplicateId { error, .. } => error,
+        }
+    }
+
+    fn on_line(self, line: usize) -> ClaimError {
+        match self {
+            ClaimError::Malformed(error) => ClaimError::Malformed(error.on_line(line)),
+            ClaimError::ZeroArea(error) => ClaimError::ZeroArea(error.on_line(line)),
+            ClaimError::Overflow(error) => ClaimError::Overflow(error.on_line(line)),
+            ClaimError::DuplicateId {
+                id,
+                first_line,
+                error,
+            } => ClaimError::DuplicateId {
+                id,
+                first_line,
+                error: error.on_line(line),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.parse_error().fmt(f)
+    }
+}
+
+impl Error for ClaimError {}
+
+impl From<ClaimError> for ParseError {
+    fn from(error: ClaimError) -> ParseError {
+        match error {
+            ClaimError::Malformed(error)
+            | ClaimError::ZeroArea(error)
+            | ClaimError::Overflow(error)
+            | ClaimError::DuplicateId { error, .. } => error,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Claim {
+    pub id: u32,
+    pub start_row: u32,
+    pub start_column: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+// Returns the claim together with the byte offset of its id, which errors
+// about the id point at.
+fn parse_claim(claim_def: &str) -> Result<(Claim, usize), ClaimError> {
+    lazy_static! {
+        static ref CLAIM_RE: Regex =
+            Regex::new(r"^\s*#\s*(\d+)\s*@\s*(\d+)\s*,\s*(\d+)\s*:\s*(\d+)\s*x\s*(\d+)\s*$")
+                .unwrap();
+    }
+
+    let caps = CLAIM_RE.captures(claim_def).ok_or_else(|| {
+        ClaimError::Malformed(ParseError::mismatch(
+            claim_def,
+            "a claim like `#1 @ 3,2: 5x4`",
+        ))
+    })?;
+
+    let fields = [
+        "a claim id",
+        "a left edge",
+        "a top edge",
+        "a width",
+        "a height",
+    ];
+    let mut results = Vec::new();
+    for (i, expected) in fields.iter().enumerate() {
+        let field = caps.get(i + 1).unwrap();
+        results.push(
+            parse_field::<u32>(claim_def, field.start(), field.as_str(), expected)
+                .map_err(ClaimError::Malformed)?,
+        );
+    }
+
+    let (left, top, width, height) = (results[1], results[2], results[3], results[4]);
+    for (group, start, size, name) in [(4, left, width, "width"), (5, top, height, "height")] {
+        let field = caps.get(group).unwrap();
+        let error =
+            |expected: String| ParseError::new(claim_def, field.start(), field.as_str(), &expected);
+        if size == 0 {
+            let expected = format!("a {} of at least 1", name);
+            return Err(ClaimError::ZeroArea(error(expected)));
+        }
+        if start.checked_add(size).is_none() {
+            let expected = format!("a {} of at most {}", name, u32::MAX - start);
+            return Err(ClaimError::Overflow(error(expected)));
+        }
+    }
+
+    let claim = Claim {
+        id: results[0],
+        start_row: top,
+        start_column: left,
+        width,
+        height,
+    };
+    Ok((claim, caps.get(1).unwrap().start()))
+}
+
+impl FromStr for Claim {
+    type Err = ClaimError;
+
+    fn from_str(claim_def: &str) -> Result<Claim, ClaimError> {
+        parse_claim(claim_def).map(|(claim, _)| claim)
+    }
+}
+
+impl Claim {
+    pub fn new(claim_def: &str) -> Result<Claim, ParseError> {
+        Ok(claim_def.parse::<Claim>()?)
+    }
+
+    /// The inches the claim covers. `None` for claims past `i32::MAX`, which
+    /// don't fit on a grid and are left to the sweep engine.
+    pub fn bounds(&self) -> Option<Bounds> {
+        let last = |start: u32, size: u32| i32::try_from(i64::from(start) + i64::from(size) - 1);
+        let min = Point::new(
+            i32::try_from(self.start_column).ok()?,
+            i32::try_from(self.start_row).ok()?,
+        );
+        let max = Point::new(
+            last(self.start_column, self.width).ok()?,
+            last(self.start_row, self.height).ok()?,
+        );
+        Some(Bounds::new(min, max))
+    }
+
+    /// Claims that don't fit on a grid aren't drawn.
+    pub fn draw_on(&self, canvas: &mut Canvas) {
+        if let Some(bounds) = self.bounds() {
+            canvas.draw(bounds);
+        }
+    }
+
+    pub fn values_on_canvas(&self, canvas: &Canvas) -> Vec<u16> {
+        match self.bounds() {
+            Some(bounds) => bounds.points().map(|p| canvas.get(p)).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Parses every line into a claim, making sure no two have the same id.
+pub fn parse_claims(input: &str) -> Result<Vec<Claim>, ClaimError> {
+    let mut first_lines = HashMap::new();
+    let mut claims = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let (claim, id_start) = parse_claim(line).map_err(|e| e.on_line(i + 1))?;
+
+        if let Some(&first_line) = first_lines.get(&claim.id) {
+            let id = claim.id.to_string();
+            let expected = format!("a new claim id, #{} is on line {} already", id, first_line);
+            return Err(ClaimError::DuplicateId {
+                id: claim.id,
+                first_line,
+                error: ParseError::new(line, id_start, &id, &expected).on_line(i + 1),
+            });
+        }
+        first_lines.insert(claim.id, i + 1);
+        claims.push(claim);
+    }
+
+    Ok(claims)
+}
+
+// A canvas of up to this many square inches, 32 MiB of counts, is drawn on.
+// Claims spread out further than that are swept instead.
+const DENSE_LIMIT: u64 = 1 << 24;
+
+/// How the overlaps between claims are found.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Engine {
+    /// Draws every claim on a canvas covering all of them.
+    Dense,
+    /// Sweeps across the edges of the claims, which only takes memory for
+    /// the claims themselves, however far apart they are.
+    Sweep,
+}
+
+impl Engine {
+    pub fn for_claims(claims: &[Claim]) -> Engine {
+        let claims = claims.iter().filter(|c| c.width > 0 && c.height > 0);
+        let left = claims.clone().map(|c| u64::from(c.start_column)).min();
+        let top = claims.clone().map(|c| u64::from(c.start_row)).min();
+        let right = claims
+            .clone()
+            .map(|c| u64::from(c.start_column) + u64::from(c.width))
+            .max();
+        let bottom = claims
+            .map(|c| u64::from(c.start_row) + u64::from(c.height))
+            .max();
+
+        match (left, top, right, bottom) {
+            (Some(left), Some(top), Some(right), Some(bottom)) => {
+                let fits = right <= i32::MAX as u64 && bottom <= i32::MAX as u64;
+                if fits && (right - left) * (bottom - top) <= DENSE_LIMIT {
+                    Engine::Dense
+                } else {
+                    Engine::Sweep
+                }
+            }
+            _ => Engine::Dense,
+        }
+    }
+
+    pub fn overlaps(self, claims: &[Claim]) -> Overlaps {
+        match self {
+            Engine::Dense => {
+                let canvas = draw_claims(claims);
+                Overlaps {
+                    area: canvas.values().filter(|v| *v > 1).count() as u64,
+                    intact: claims
+                        .iter()
+                        .filter(|c| c.values_on_canvas(&canvas).iter().all(|v| *v == 1))
+                        .map(|c| c.id)
+                        .collect(),
+                }
+            }
+            Engine::Sweep => Overlaps {
+                area: sweep::overlapping_area(claims),
+                intact: sweep::intact(claims),
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Overlaps {
+    /// Square inches covered by two or more claims.
+    pub area: u64,
+    /// Claims that don't overlap with any other, in input order.
+    pub intact: Vec<u32>,
+}
+
+/// A canvas just big enough for all claims, with all of them drawn on it.
+pub fn draw_claims(claims: &[Claim]) -> Canvas {
+    let corners = claims
+        .iter()
+        .filter(|c| c.width > 0 && c.height > 0)
+        .filter_map(Claim::bounds)
+        .flat_map(|bounds| vec![bounds.min, bounds.max]);
+    let mut canvas = match Bounds::of(corners) {
+        Some(bounds) => Canvas::covering(bounds),
+        None => Canvas::new(),
+    };
+    for claim in claims {
+        claim.draw_on(&mut canvas);
+    }
+    canvas
+}
+
+pub fn overlaps(claims: &[Claim]) -> Overlaps {
+    Engine::for_claims(claims).overlaps(claims)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Overlaps;
+
+    fn parse(input: &str) -> Result<Overlaps, ParseError> {
+        Ok(overlaps(&parse_claims(input)?))
+    }
+
+    fn part_1(overlaps: &Overlaps) -> Answer {
+        Answer::new(overlaps.area)
+    }
+
+    fn part_2(overlaps: &Overlaps) -> Answer {
+        let intact = overlaps
+            .intact
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        Answer::new(intact)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn claim_new() {
+        let input = "#123 @ 3,2: 5x4";
+        let claim = Claim::new(input).unwrap();
+
+        assert_eq!(claim.id, 123);
+        assert_eq!(claim.start_row, 2);
+        assert_eq!(claim.start_column, 3);
+        assert_eq!(claim.width, 5);
+        assert_eq!(claim.height, 4);
+
+        let input_2 = "#1353 @ 370,944: 26x15";
+        let claim_2 = Claim::new(input_2).unwrap();
+
+        assert_eq!(claim_2.id, 1353);
+        assert_eq!(claim_2.start_row, 944);
+        assert_eq!(claim_2.start_column, 370);
+        assert_eq!(claim_2.width, 26);
+        assert_eq!(claim_2.height, 15);
+    }
+
+    #[test]
+    fn claim_draw_on_canvas() {
+        // ........
+        // ...2222.
+        // ...2222.
+        // .11XX22.
+        // .11XX22.
+        // .111133.
+        // .111133.
+        // ........
+        let claim_1 = Claim::new("#1 @ 1,3: 4x4").unwrap();
+        let claim_2 = Claim::new("#2 @ 3,1: 4x4").unwrap();
+        let claim_3 = Claim::new("#3 @ 5,5: 2x2").unwrap();
+
+        let mut expected = Canvas::new();
+        expected.insert(Point::new(3, 1), 1);
+        expected.insert(Point::new(4, 1), 1);
+        expected.insert(Point::new(5, 1), 1);
+        expected.insert(Point::new(6, 1), 1);
+
+        expected.insert(Point::new(3, 2), 1);
+        expected.insert(Point::new(4, 2), 1);
+        expected.insert(Point::new(5, 2), 1);
+        expected.insert(Point::new(6, 2), 1);
+
+        expected.insert(Point::new(1, 3), 1);
+        expected.insert(Point::new(2, 3), 1);
+        expected.insert(Point::new(3, 3), 2);
+        expected.insert(Point::new(4, 3), 2);
+        expected.insert(Point::new(5, 3), 1);
+        expected.insert(Point::new(6, 3), 1);
+
+        expected.insert(Point::new(1, 4), 1);
+        expected.insert(Point::new(2, 4), 1);
+        expected.insert(Point::new(3, 4), 2);
+        expected.insert(Point::new(4, 4), 2);
+        expected.insert(Point::new(5, 4), 1);
+        expected.insert(Point::new(6, 4), 1);
+
+        expected.insert(Point::new(1, 5), 1);
+        expected.insert(Point::new(2, 5), 1);
+        expected.insert(Point::new(3, 5), 1);
+        expected.insert(Point::new(4, 5), 1);
+        expected.insert(Point::new(5, 5), 1);
+        expected.insert(Point::new(6, 5), 1);
+
+        expected.insert(Point::new(1, 6), 1);
+        expected.insert(Point::new(2, 6), 1);
+        expected.insert(Point::new(3, 6), 1);
+        expected.insert(Point::new(4, 6), 1);
+        expected.insert(Point::new(5, 6), 1);
+        expected.insert(Point::new(6, 6), 1);
+
+        let mut canvas = Canvas::new();
+        claim_1.draw_on(&mut canvas);
+        claim_2.draw_on(&mut canvas);
+        claim_3.draw_on(&mut canvas);
+
+        assert_eq!(canvas, expected);
+    }
+
+    #[test]
+    fn claim_get_values() {
+        // ........
+        // ...2222.
+        // ...2222.
+        // .11XX22.
+        // .11XX22.
+        // .111133.
+        // .111133.
+        // ........
+        let claim_1 = Claim::new("#1 @ 1,3: 4x4").unwrap();
+        let claim_2 = Claim::new("#2 @ 3,1: 4x4").unwrap();
+        let claim_3 = Claim::new("#3 @ 5,5: 2x2").unwrap();
+
+        let mut canvas = Canvas::new();
+        claim_1.draw_on(&mut canvas);
+        claim_2.draw_on(&mut canvas);
+        claim_3.draw_on(&mut canvas);
+
+        let claim_1_values = claim_1.values_on_canvas(&canvas);
+        assert_eq!(claim_1_values.iter().filter(|v| **v == 1).count(), 12);
+        assert_eq!(claim_1_values.iter().filter(|v| **v == 2).count(), 4);
+
+        let claim_2_values = claim_2.values_on_canvas(&canvas);
+        assert_eq!(claim_2_values.iter().filter(|v| **v == 1).count(), 12);
+        assert_eq!(claim_2_values.iter().filter(|v| **v == 2).count(), 4);
+
+        let claim_3_values = claim_3.values_on_canvas(&canvas);
+        assert_eq!(claim_3_values.iter().filter(|v| **v == 1).count(), 4);
+        assert_eq!(claim_3_values.iter().filter(|v| **v == 2).count(), 0);
+    }
+
+    #[test]
+    fn claim_parse_errors() {
+        let error = Claim::new("#1 @ 1,3 4x4").unwrap_err();
+        assert_eq!(error.column, 1);
+        assert_eq!(error.found, "#1 @ 1,3 4x4");
+
+        let error = Claim::new("#1 @ 1,3: 4x99999999999").unwrap_err();
+        assert_eq!(error.column, 13);
+        assert_eq!(error.found, "99999999999");
+        assert_eq!(error.expected, "a height");
+
+        let error = parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1 4x4").unwrap_err();
+        assert_eq!(error.parse_error().line, 2);
+    }
+
+    fn example() -> Vec<Claim> {
+        parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2").unwrap()
+    }
+
+    #[test]
+    fn choosing_an_engine() {
+        assert_eq!(Engine::for_claims(&example()), Engine::Dense);
+        assert_eq!(Engine::for_claims(&[]), Engine::Dense);
+
+        let spread = parse_claims("#1 @ 0,0: 2x2\n#2 @ 100000,100000: 2x2").unwrap();
+        assert_eq!(Engine::for_claims(&spread), Engine::Sweep);
+
+        let far_out = parse_claims("#1 @ 4294967000,0: 200x200").unwrap();
+        assert_eq!(Engine::for_claims(&far_out), Engine::Sweep);
+    }
+
+    #[test]
+    fn finding_overlaps() {
+        let expected = Overlaps {
+            area: 4,
+            intact: vec![3],
+        };
+        assert_eq!(Engine::Dense.overlaps(&example()), expected);
+        assert_eq!(Engine::Sweep.overlaps(&example()), expected);
+    }
+
+    // A simple linear congruential generator, so the claims are the same on
+    // every run. Each call returns a number below `max`.
+    fn random(seed: u64) -> impl FnMut(u64) -> u32 {
+        let mut seed = seed;
+        move |max| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((seed >> 33) % max) as u32
+        }
+    }
+
+    #[test]
+    fn engines_agree() {
+        let mut random = random(42);
+
+        for _ in 0..20 {
+            let claims: Vec<Claim> = (1..=50)
+                .map(|id| Claim {
+                    id,
+                    start_column: random(60),
+                    start_row: random(60),
+                    width: random(15),
+                    height: random(15),
+                })
+                .collect();
+
+            assert_eq!(
+                Engine::Dense.overlaps(&claims),
+                Engine::Sweep.overlaps(&claims)
+            );
+        }
+    }
+
+    #[test]
+    fn querying_conflicts() {
+        // #4 overlaps with #3 in the bottom right corner of the example.
+        let mut claims = example();
+        claims.push(Claim::new("#4 @ 6,6: 3x3").unwrap());
+        claims.push(Claim::new("#5 @ 20,20: 1x1").unwrap());
+        let graph = OverlapGraph::new(&claims);
+
+        assert_eq!(graph.conflicts(1), Some(&[Conflict { id: 2, area: 4 }][..]));
+        assert_eq!(graph.conflicts(3), Some(&[Conflict { id: 4, area: 1 }][..]));
+        assert_eq!(graph.conflicts(5), Some(&[][..]));
+        assert_eq!(graph.conflicts(6), None);
+
+        assert_eq!(graph.components(), vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn exporting_to_dot() {
+        let graph = OverlapGraph::new(&example());
+        assert_eq!(
+            graph.to_dot(),
+            "graph claims {\n    1 -- 2 [label=\"4\"];\n    3 [style=filled, fillcolor=palegreen];\n}\n"
+        );
+    }
+
+    #[test]
+    fn rendering_the_canvas() {
+        let claims = example();
+        let image = Image::render(&draw_claims(&claims), &claims, false);
+        assert_eq!((image.width, image.height), (6, 6));
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n6 6\n255\n"));
+        assert_eq!(ppm.len(), 11 + 6 * 6 * 3);
+
+        // The canvas starts at 1,1, so that's the first pixel.
+        let pixel = |x: usize, y: usize| {
+            let i = 11 + (y * 6 + x) * 3;
+            ppm[i..i + 3].to_vec()
+        };
+        assert_eq!(pixel(0, 0), vec![20, 20, 30]);
+        assert_eq!(pixel(2, 0), vec![40, 70, 140]);
+        assert_eq!(pixel(2, 2), vec![255, 230, 0]);
+        assert_eq!(pixel(5, 5), vec![60, 220, 90]);
+
+        let outlined = Image::render(&draw_claims(&claims), &claims, true);
+        let mut png = Vec::new();
+        outlined.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn adding_and_removing_claims() {
+        let mut fabric = Fabric::new();
+        for claim in example() {
+            fabric.insert(claim).unwrap();
+        }
+        assert_eq!(fabric.overlap(), 4);
+        assert_eq!(fabric.intact().collect::<Vec<u32>>(), vec![3]);
+
+        assert_eq!(
+            fabric.insert(Claim::new("#2 @ 0,0: 1x1").unwrap()),
+            Err(FabricError::DuplicateId(2))
+        );
+        assert_eq!(fabric.len(), 3);
+
+        assert_eq!(fabric.remove(2).map(|c| c.id), Some(2));
+        assert!(fabric.remove(2).is_none());
+        assert_eq!(fabric.overlap(), 0);
+        assert_eq!(fabric.intact().collect::<Vec<u32>>(), vec![1, 3]);
+
+        fabric
+            .insert(Claim::new("#4 @ 0,0: 20x20").unwrap())
+            .unwrap();
+        assert_eq!(fabric.overlap(), 16 + 4);
+        assert_eq!(fabric.intact().count(), 0);
+    }
+
+    #[test]
+    fn fabric_with_claims_far_apart() {
+        let mut fabric = Fabric::new();
+        fabric.insert(Claim::new("#1 @ 0,0: 2x2").unwrap()).unwrap();
+        fabric
+            .insert(Claim::new("#2 @ 100000,100000: 2x2").unwrap())
+            .unwrap();
+        fabric
+            .insert(Claim::new("#3 @ 100001,100001: 2x2").unwrap())
+            .unwrap();
+        assert_eq!(fabric.overlap(), 1);
+        assert_eq!(fabric.intact().collect::<Vec<u32>>(), vec![1]);
+
+        fabric.remove(2);
+        assert_eq!(fabric.overlap(), 0);
+        assert_eq!(fabric.intact().collect::<Vec<u32>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn claims_past_the_grid() {
+        let claim = Claim::new("#1 @ 2147483600,0: 100x1").unwrap();
+        assert_eq!(claim.bounds(), None);
+
+        let claim = Claim::new("#2 @ 2147483600,0: 48x1").unwrap();
+        assert_eq!(claim.bounds().map(|b| b.max.x), Some(i32::MAX));
+    }
+
+    #[test]
+    fn fabric_rejects_claims_past_the_grid() {
+        let mut fabric = Fabric::new();
+        let claim = Claim::new("#1 @ 2147483600,0: 100x1").unwrap();
+        assert_eq!(fabric.insert(claim), Err(FabricError::OffTheGrid(1)));
+        assert!(fabric.is_empty());
+
+        let claim = Claim::new("#2 @ 2147483600,0: 48x1").unwrap();
+        fabric.insert(claim).unwrap();
+        assert_eq!(fabric.intact().collect::<Vec<u32>>(), vec![2]);
+    }
+
+    #[test]
+    fn fabric_agrees_with_the_engines() {
+        let mut random = random(7);
+
+        let mut fabric = Fabric::new();
+        let mut claims: Vec<Claim> = Vec::new();
+        for id in 1..=200 {
+            // Every third step takes back an earlier claim instead.
+            if id % 3 == 0 && !claims.is_empty() {
+                let removed = claims.remove(random(claims.len() as u64) as usize);
+                assert!(fabric.remove(removed.id).is_some());
+            } else {
+                let claim = Claim {
+                    id,
+                    start_column: random(40),
+                    start_row: random(40),
+                    width: random(10),
+                    height: random(10),
+                };
+                fabric.insert(claim.clone()).unwrap();
+                claims.push(claim);
+            }
+
+            let expected = Engine::Dense.overlaps(&claims);
+            let mut intact = expected.intact;
+            intact.sort_unstable();
+            assert_eq!(fabric.overlap(), expected.area);
+            assert_eq!(fabric.intact().collect::<Vec<u32>>(), intact);
+        }
+    }
+
+    #[test]
+    fn claim_from_str() {
+        let claim: Claim = "  #7 @ 3 , 2 :5 x 4 ".parse().unwrap();
+        assert_eq!((claim.id, claim.start_column, claim.start_row), (7, 3, 2));
+        assert_eq!((claim.width, claim.height), (5, 4));
+
+        match "#1 @ 1,3 4x4 trailing".parse::<Claim>() {
+            Err(ClaimError::Malformed(error)) => assert_eq!(error.column, 1),
+            other => panic!("expected a malformed claim, got {:?}", other),
+        }
+
+        match "#1 @ 1,3: 4x0".parse::<Claim>() {
+            Err(ClaimError::ZeroArea(error)) => {
+                assert_eq!(error.column, 13);
+                assert_eq!(error.expected, "a height of at least 1");
+            }
+            other => panic!("expected a zero area claim, got {:?}", other),
+        }
+
+        match "#1 @ 4294967000,3: 296x1".parse::<Claim>() {
+            Err(ClaimError::Overflow(error)) => {
+                assert_eq!(error.found, "296");
+                assert_eq!(error.expected, "a width of at most 295");
+            }
+            other => panic!("expected an overflowing claim, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn duplicate_claim_ids() {
+        let error = parse_claims("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n# 1 @ 5,5: 2x2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a new claim id, #1 is on line 1 already, found `1`"
+        );
+        match error {
+            ClaimError::DuplicateId {
+                id,
+                first_line,
+                error,
+            } => {
+                assert_eq!((id, first_line), (1, 1));
+                assert_eq!((error.line, error.column), (3, 3));
+            }
+            other => panic!("expected a duplicate id, got {:?}", other),
+        }
+    }
+}
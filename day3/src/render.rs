@@ -0,0 +1,133 @@
+use crate::{Canvas, Claim};
+use grid::Point;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [20, 20, 30];
+const CLAIMED: Rgb = [40, 70, 140];
+const INTACT: Rgb = [60, 220, 90];
+
+/// A picture of a canvas, one pixel per square inch.
+#[derive(Debug)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<Rgb>,
+}
+
+// From yellow for two claims to red for the most claims on any inch.
+fn heat(count: u16, max: u16) -> Rgb {
+    let share = if max > 2 {
+        f64::from(count - 2) / f64::from(max - 2)
+    } else {
+        0.0
+    };
+    [255, (230.0 * (1.0 - share)) as u8, 0]
+}
+
+// Neighbouring ids get colours far apart, so that the outlines of
+// overlapping claims can be told apart.
+fn outline_colour(id: u32) -> Rgb {
+    let hue = (f64::from(id) * 0.618_033_988_75).fract() * 6.0;
+    let x = (255.0 * (1.0 - (hue % 2.0 - 1.0).abs())) as u8;
+    match hue as u32 {
+        0 => [255, x, 0],
+        1 => [x, 255, 0],
+        2 => [0, 255, x],
+        3 => [0, x, 255],
+        4 => [x, 0, 255],
+        _ => [255, 0, x],
+    }
+}
+
+impl Image {
+    /// Colours every square inch of `canvas` by how many claims cover it.
+    /// Intact claims are green, and with `outlines` every claim gets a
+    /// border in a colour of its own.
+    pub fn render(canvas: &Canvas, claims: &[Claim], outlines: bool) -> Image {
+        let bounds = match canvas.bounds() {
+            Some(bounds) => bounds,
+            None => {
+                return Image {
+                    width: 0,
+                    height: 0,
+                    pixels: Vec::new(),
+                }
+            }
+        };
+        let max = canvas.values().max().unwrap_or(0);
+
+        let mut pixels: Vec<Rgb> = bounds
+            .points()
+            .map(|p| match canvas.get(p) {
+                0 => BACKGROUND,
+                1 => CLAIMED,
+                count => heat(count, max),
+            })
+            .collect();
+        let mut paint = |p: Point, colour: Rgb| {
+            let i = (p.y - bounds.min.y) * bounds.width() + (p.x - bounds.min.x);
+            pixels[i as usize] = colour;
+        };
+
+        for claim in claims {
+            let bounds = match claim.bounds() {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            if claim.values_on_canvas(canvas).iter().all(|v| *v == 1) {
+                for p in bounds.points() {
+                    paint(p, INTACT);
+                }
+            }
+        }
+        if outlines {
+            for claim in claims {
+                let claim_bounds = match claim.bounds() {
+                    Some(bounds) => bounds,
+                    None => continue,
+                };
+                for p in claim_bounds.points().filter(|p| claim_bounds.is_edge(*p)) {
+                    paint(p, outline_colour(claim.id));
+                }
+            }
+        }
+
+        Image {
+            width: bounds.width() as u32,
+            height: bounds.height() as u32,
+            pixels,
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|rgb| rgb.to_vec()).collect()
+    }
+
+    /// Writes a binary PPM, which needs nothing but a header.
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.bytes())
+    }
+
+    pub fn write_png<W: Write>(&self, out: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.bytes())?;
+        Ok(())
+    }
+
+    /// Saves the image as a PNG if `path` ends in `.png`, as a PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let out = BufWriter::new(File::create(path)?);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("png") => self.write_png(out),
+            _ => self.write_ppm(out),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::Claim;
+
+// The edges of a claim, with `right` and `bottom` just outside of it. They
+// are u64 so that `start + width` never overflows.
+#[derive(Clone, Copy)]
+struct Rect {
+    left: u64,
+    top: u64,
+    right: u64,
+    bottom: u64,
+}
+
+impl Rect {
+    fn of(claim: &Claim) -> Rect {
+        Rect {
+            left: u64::from(claim.start_column),
+            top: u64::from(claim.start_row),
+            right: u64::from(claim.start_column) + u64::from(claim.width),
+            bottom: u64::from(claim.start_row) + u64::from(claim.height),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.left == self.right || self.top == self.bottom
+    }
+}
+
+// Claims without any area can't overlap with anything.
+fn rects(claims: &[Claim]) -> Vec<Rect> {
+    let mut rects: Vec<Rect> = claims
+        .iter()
+        .map(Rect::of)
+        .filter(|r| !r.is_empty())
+        .collect();
+    rects.sort_by_key(|r| r.left);
+    rects
+}
+
+/// The number of square inches covered by two or more claims. Only the
+/// columns where a claim starts or ends are looked at: between two of them,
+/// every column is covered by the same claims.
+pub fn overlapping_area(claims: &[Claim]) -> u64 {
+    let rects = rects(claims);
+
+    let mut columns: Vec<u64> = rects.iter().flat_map(|r| vec![r.left, r.right]).collect();
+    columns.sort_unstable();
+    columns.dedup();
+
+    let mut area = 0;
+    let mut next = 0;
+    let mut active: Vec<Rect> = Vec::new();
+    for pair in columns.windows(2) {
+        let (left, right) = (pair[0], pair[1]);
+
+        active.retain(|r| r.right > left);
+        while next < rects.len() && rects[next].left == left {
+            active.push(rects[next]);
+            next += 1;
+        }
+
+        // Sweep down the slab, counting how many claims cover each row.
+        let mut edges: Vec<(u64, i32)> = active
+            .iter()
+            .flat_map(|r| vec![(r.top, 1), (r.bottom, -1)])
+            .collect();
+        edges.sort_unstable();
+
+        let mut covered = 0;
+        let mut depth = 0;
+        let mut previous = 0;
+        for (row, change) in edges {
+            if depth >= 2 {
+                covered += row - previous;
+            }
+            depth += change;
+            previous = row;
+        }
+
+        area += covered * (right - left);
+    }
+    area
+}
+
+/// Every pair of claims that overlap, as their indices in `claims` and the
+/// number of square inches they share.
+pub fn overlapping_pairs(claims: &[Claim]) -> Vec<(usize, usize, u64)> {
+    let mut rects: Vec<(usize, Rect)> = claims
+        .iter()
+        .map(Rect::of)
+        .enumerate()
+        .filter(|(_, r)| !r.is_empty())
+        .collect();
+    rects.sort_by_key(|(_, r)| r.left);
+
+    let mut pairs = Vec::new();
+    for (i, (a, rect_a)) in rects.iter().enumerate() {
+        // Sorted by their left edge, the claims that can overlap with `a`
+        // are the ones right after it that start before it ends.
+        for (b, rect_b) in rects[i + 1..]
+            .iter()
+            .take_while(|(_, r)| r.left < rect_a.right)
+        {
+            let width = rect_a.right.min(rect_b.right) - rect_b.left;
+            let top = rect_a.top.max(rect_b.top);
+            let bottom = rect_a.bottom.min(rect_b.bottom);
+            if top < bottom {
+                pairs.push(((*a).min(*b), (*a).max(*b), width * (bottom - top)));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The ids of the claims that don't overlap with any other claim.
+pub fn intact(claims: &[Claim]) -> Vec<u32> {
+    let mut overlapping = vec![false; claims.len()];
+    for (a, b, _) in overlapping_pairs(claims) {
+        overlapping[a] = true;
+        overlapping[b] = true;
+    }
+
+    claims
+        .iter()
+        .zip(overlapping)
+        .filter(|(_, overlapping)| !overlapping)
+        .map(|(claim, _)| claim.id)
+        .collect()
+}
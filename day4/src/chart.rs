@@ -0,0 +1,268 @@
+use crate::window::clock;
+use crate::{midnight, Log, LogType, Window};
+use chrono::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// One guard's shift, with a flag for every minute of the window telling
+/// whether the guard was asleep.
+#[derive(Debug, PartialEq)]
+pub struct Night {
+    pub date: NaiveDate,
+    pub guard: u32,
+    pub asleep: Vec<bool>,
+}
+
+/// Which nights to show. Both dates are included.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Filter {
+    pub guard: Option<u32>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl Filter {
+    pub fn matches(&self, night: &Night) -> bool {
+        self.guard.is_none_or(|guard| guard == night.guard)
+            && self.from.is_none_or(|from| night.date >= from)
+            && self.to.is_none_or(|to| night.date <= to)
+    }
+}
+
+/// Replays the sorted, checked logs night by night.
+pub fn nights(logs: &[Log], window: Window) -> Vec<Night> {
+    let minutes = (window.end - window.start) as usize;
+    let mut nights: Vec<Night> = Vec::new();
+    let mut sleep_start = None;
+
+    for log in logs {
+        match log.log_type {
+            LogType::ShiftStart(guard) => nights.push(Night {
+                date: midnight(log.time).date_naive(),
+                guard,
+                asleep: vec![false; minutes],
+            }),
+            LogType::SleepStart => sleep_start = Some(log.time),
+            LogType::SleepEnd => {
+                let (night, start) = match (nights.last_mut(), sleep_start.take()) {
+                    (Some(night), Some(start)) => (night, start),
+                    _ => continue,
+                };
+                let midnight = midnight(start);
+                let start = (start - midnight).num_minutes();
+                let end = (log.time - midnight).num_minutes();
+                for minute in start.max(window.start)..end.min(window.end) {
+                    night.asleep[(minute - window.start) as usize] = true;
+                }
+            }
+        }
+    }
+
+    nights
+}
+
+/// The chart from the puzzle description, with a line per night:
+///
+/// ```text
+/// Date   ID   Minute
+///             000000000011111111112222222222333333333344444444445555555555
+///             012345678901234567890123456789012345678901234567890123456789
+/// 11-01  #10  .....####################.....#########################.....
+/// ```
+pub fn chart(nights: &[Night], window: Window) -> String {
+    let width = nights
+        .iter()
+        .map(|n| n.guard.to_string().len() + 1)
+        .max()
+        .unwrap_or(2);
+    let minutes: Vec<i64> = (window.start..window.end)
+        .map(|m| m.rem_euclid(60))
+        .collect();
+    let digits = |digit: fn(i64) -> i64| -> String {
+        minutes
+            .iter()
+            .map(|m| std::char::from_digit(digit(*m) as u32, 10).unwrap())
+            .collect()
+    };
+
+    let mut chart = String::new();
+    writeln!(chart, "Date   {:<w$}  Minute", "ID", w = width).unwrap();
+    writeln!(
+        chart,
+        "       {:<w$}  {}",
+        "",
+        digits(|m| m / 10),
+        w = width
+    )
+    .unwrap();
+    writeln!(
+        chart,
+        "       {:<w$}  {}",
+        "",
+        digits(|m| m % 10),
+        w = width
+    )
+    .unwrap();
+    for night in nights {
+        let asleep: String = night
+            .asleep
+            .iter()
+            .map(|&asleep| if asleep { '#' } else { '.' })
+            .collect();
+        writeln!(
+            chart,
+            "{}  {:<w$}  {}",
+            night.date.format("%m-%d"),
+            format!("#{}", night.guard),
+            asleep,
+            w = width
+        )
+        .unwrap();
+    }
+    chart
+}
+
+const CELL_WIDTH: usize = 12;
+const CELL_HEIGHT: usize = 18;
+const LEFT: usize = 70;
+const TOP: usize = 40;
+
+/// An HTML page with an SVG heatmap of how many nights each guard was asleep
+/// at each minute of the window.
+pub fn heatmap(nights: &[Night], window: Window) -> String {
+    let minutes = (window.end - window.start) as usize;
+    let mut counts: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for night in nights {
+        let row = counts
+            .entry(night.guard)
+            .or_insert_with(|| vec![0; minutes]);
+        for (count, &asleep) in row.iter_mut().zip(&night.asleep) {
+            *count += asleep as u32;
+        }
+    }
+    let max = counts.values().flatten().cloned().max().unwrap_or(0).max(1);
+
+    let width = LEFT + minutes * CELL_WIDTH + 10;
+    let height = TOP + counts.len() * CELL_HEIGHT + 10;
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="11">"#,
+        width, height
+    )
+    .unwrap();
+
+    for i in (0..minutes).filter(|i| (window.start + *i as i64).rem_euclid(5) == 0) {
+        writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            LEFT + i * CELL_WIDTH + CELL_WIDTH / 2,
+            TOP - 10,
+            clock(window.start + i as i64)
+        )
+        .unwrap();
+    }
+
+    for (row, (guard, counts)) in counts.iter().enumerate() {
+        let y = TOP + row * CELL_HEIGHT;
+        writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" text-anchor="end">#{}</text>"#,
+            LEFT - 6,
+            y + CELL_HEIGHT - 5,
+            guard
+        )
+        .unwrap();
+
+        for (i, &count) in counts.iter().enumerate() {
+            // From white for never to deep red for the most nights.
+            let fade = 255 - (225 * count / max) as u8;
+            writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="rgb(255,{},{})"><title>#{} at {}: {} night(s)</title></rect>"#,
+                LEFT + i * CELL_WIDTH,
+                y,
+                CELL_WIDTH,
+                CELL_HEIGHT,
+                fade,
+                fade,
+                guard,
+                clock(window.start + i as i64),
+                count
+            )
+            .unwrap();
+        }
+    }
+    svg.push_str("</svg>\n");
+
+    let dates = match (nights.first(), nights.last()) {
+        (Some(first), Some(last)) => format!("from {} to {}", first.date, last.date),
+        _ => String::from("none at all"),
+    };
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Guards asleep</title>\n</head>\n<body>\n<h1>Guards asleep per minute</h1>\n<p>{} night(s), {}. The darker, the more nights the guard was asleep at that minute.</p>\n{}</body>\n</html>\n",
+        nights.len(),
+        dates,
+        svg
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_logs, EXAMPLE};
+
+    #[test]
+    fn charting_nights() {
+        let logs = parse_logs(EXAMPLE.to_vec()).unwrap();
+        let nights = nights(&logs, Window::default());
+        assert_eq!(nights.len(), 5);
+
+        assert_eq!(
+            chart(&nights, Window::default()),
+            "\
+Date   ID   Minute
+            000000000011111111112222222222333333333344444444445555555555
+            012345678901234567890123456789012345678901234567890123456789
+11-01  #10  .....####################.....#########################.....
+11-02  #99  ........................................##########..........
+11-03  #10  ........................#####...............................
+11-04  #99  ....................................##########..............
+11-05  #99  .............................................##########.....
+"
+        );
+
+        let filter = Filter {
+            guard: Some(99),
+            from: NaiveDate::from_ymd_opt(1518, 11, 3),
+            to: None,
+        };
+        let window = Window::new(35, 50);
+        let filtered: Vec<Night> = super::nights(&logs, window)
+            .into_iter()
+            .filter(|n| filter.matches(n))
+            .collect();
+        assert_eq!(
+            chart(&filtered, window),
+            "\
+Date   ID   Minute
+            333334444444444
+            567890123456789
+11-04  #99  .##########....
+11-05  #99  ..........#####
+"
+        );
+    }
+
+    #[test]
+    fn heatmap_of_nights() {
+        let logs = parse_logs(EXAMPLE.to_vec()).unwrap();
+        let html = heatmap(&nights(&logs, Window::default()), Window::default());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("5 night(s), from 1518-11-01 to 1518-11-05"));
+        // #99 was asleep at 00:45 on all three of their nights.
+        assert!(html.contains("<title>#99 at 00:45: 3 night(s)</title>"));
+        assert!(html.contains("<title>#10 at 00:24: 2 night(s)</title>"));
+        assert_eq!(html.matches("<rect").count(), 2 * 60);
+    }
+}
@@ -0,0 +1,489 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate chrono;
+extern crate regex;
+
+use chrono::prelude::*;
+use common::output;
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod chart;
+mod stats;
+mod strategy;
+mod validate;
+mod window;
+
+pub use crate::chart::{chart, heatmap, nights, Filter, Night};
+pub use crate::stats::{
+    guard_stats, night_stats, save, to_csv, to_json, GuardStats, NightStats, Row,
+};
+pub use crate::strategy::{
+    strategies, strategy, Choice, EarliestSleeper, LongestNap, MostMinutes, MostNights, SameMinute,
+    Strategy,
+};
+pub use crate::validate::{check_logs, validate, Diagnostic, Mode, Problem};
+pub use crate::window::{midnight, Window};
+
+/// From falling asleep up to, but not including, waking up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Nap {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Nap {
+    pub fn minutes(&self) -> i64 {
+        (self.end - self.start).num_minutes()
+    }
+}
+
+pub struct Guard {
+    pub id: u32,
+    /// When each of the guard's shifts began.
+    pub shifts: Vec<DateTime<Utc>>,
+    pub naps: Vec<Nap>,
+    pub window: Window,
+    /// How often the guard was asleep in each step of the window.
+    pub minutes_asleep: Vec<u32>,
+}
+
+impl Guard {
+    fn new(id: u32, window: Window) -> Guard {
+        Guard {
+            id,
+            shifts: Vec::new(),
+            naps: Vec::new(),
+            window,
+            minutes_asleep: vec![0; window.steps()],
+        }
+    }
+
+    fn add_nap(&mut self, nap: Nap) {
+        let midnight = midnight(nap.start);
+        let start = (nap.start - midnight).num_minutes();
+        let end = (nap.end - midnight).num_minutes();
+        for minute in start..end {
+            if let Some(step) = self.window.step(minute) {
+                self.minutes_asleep[step] += 1;
+            }
+        }
+        self.naps.push(nap);
+    }
+
+    /// All minutes asleep, inside of the window or not.
+    pub fn sum_minutes_asleep(&self) -> u32 {
+        self.naps.iter().map(|nap| nap.minutes() as u32).sum()
+    }
+
+    /// The first minute after midnight of the step the guard was asleep in
+    /// most often. Ties go to the earliest one.
+    pub fn sleepiest_minute(&self) -> i64 {
+        let mut i = 0;
+
+        for (j, &value) in self.minutes_asleep.iter().enumerate() {
+            if value > self.minutes_asleep[i] {
+                i = j;
+            }
+        }
+
+        self.window.minute(i)
+    }
+
+    pub fn highest_sleep(&self) -> u32 {
+        self.minutes_asleep.iter().cloned().max().unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogType {
+    SleepStart,
+    SleepEnd,
+    ShiftStart(u32),
+}
+
+#[derive(Clone, Debug)]
+pub struct Log {
+    pub log_type: LogType,
+    pub time: DateTime<Utc>,
+}
+
+pub fn parse_log_line(line: &str) -> Result<Log, ParseError> {
+    lazy_static! {
+        static ref LINE_RE: Regex =
+            Regex::new(r"\[(\d+)-(\d+)-(\d+)\s(\d+):(\d+)\]\s(.*)").unwrap();
+        static ref SHIFT_START_RE: Regex = Regex::new(r"Guard #(\d+) begins shift").unwrap();
+    }
+
+    let caps = LINE_RE.captures(line).ok_or_else(|| {
+        ParseError::mismatch(line, "a log line like `[1518-11-01 00:00] wakes up`")
+    })?;
+    let field = |i| {
+        let m: regex::Match = caps.get(i).unwrap();
+        parse_field::<u32>(line, m.start(), m.as_str(), "a number")
+    };
+
+    let year = field(1)? as i32;
+    let month = field(2)?;
+    let day = field(3)?;
+    let hour = field(4)?;
+    let minute = field(5)?;
+    let message = caps.get(6).unwrap();
+
+    let time = Utc
+        .with_ymd_and_hms(year, month, day, hour, minute, 00)
+        .single()
+        .ok_or_else(|| {
+            let start = caps.get(1).unwrap().start();
+            let end = caps.get(5).unwrap().end();
+            ParseError::new(line, start, &line[start..end], "a valid date and time")
+        })?;
+    let log_type = match message.as_str() {
+        "falls asleep" => LogType::SleepStart,
+        "wakes up" => LogType::SleepEnd,
+        shift_start => {
+            let caps = SHIFT_START_RE.captures(shift_start).ok_or_else(|| {
+                ParseError::new(
+                    line,
+                    message.start(),
+                    shift_start,
+                    "`falls asleep`, `wakes up` or `Guard #<id> begins shift`",
+                )
+            })?;
+            let id = caps.get(1).unwrap();
+            let guard_id = parse_field::<u32>(
+                line,
+                message.start() + id.start(),
+                id.as_str(),
+                "a guard id",
+            )?;
+            LogType::ShiftStart(guard_id)
+        }
+    };
+
+    Ok(Log { log_type, time })
+}
+
+pub fn parse_logs<'a, I>(lines: I) -> Result<Vec<Log>, ParseError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut parsed = Vec::new();
+
+    for (i, line) in lines.into_iter().enumerate() {
+        parsed.push(parse_log_line(line).map_err(|e| e.on_line(i + 1))?);
+    }
+
+    parsed.sort_unstable_by_key(|k| k.time);
+    Ok(parsed)
+}
+
+/// Logs that didn't go through `check_logs` can have naps without a guard
+/// or without falling asleep first, which are skipped.
+pub fn logs_to_guards(logs: Vec<Log>) -> HashMap<u32, Guard> {
+    logs_to_guards_within(logs, Window::default())
+}
+
+/// Like `logs_to_guards`, with the guards' histograms covering `window`.
+pub fn logs_to_guards_within(logs: Vec<Log>, window: Window) -> HashMap<u32, Guard> {
+    let mut guards = HashMap::new();
+
+    let mut current_id = None;
+    let mut sleep_start = None;
+
+    for log in logs.iter() {
+        match log.log_type {
+            LogType::ShiftStart(id) => {
+                let guard = guards.entry(id).or_insert_with(|| Guard::new(id, window));
+                guard.shifts.push(log.time);
+                current_id = Some(id);
+            }
+            LogType::SleepStart => {
+                sleep_start = Some(log.time);
+            }
+            LogType::SleepEnd => {
+                let (guard, start) = match (current_id, sleep_start.take()) {
+                    (Some(id), Some(start)) => (guards.get_mut(&id).unwrap(), start),
+                    _ => continue,
+                };
+                guard.add_nap(Nap {
+                    start,
+                    end: log.time,
+                });
+            }
+        }
+    }
+
+    guards
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Guard>;
+
+    fn parse(input: &str) -> Result<Vec<Guard>, ParseError> {
+        let logs = parse_logs(input.lines())?;
+        let (logs, _) = check_logs(logs, Mode::Lenient, Window::default()).unwrap();
+        Ok(logs_to_guards(logs).into_values().collect())
+    }
+
+    fn part_1(guards: &Vec<Guard>) -> Answer {
+        answer(MostMinutes.choose(guards))
+    }
+
+    fn part_2(guards: &Vec<Guard>) -> Answer {
+        answer(SameMinute.choose(guards))
+    }
+}
+
+/// A strategy's choice as the answer to a part of the puzzle.
+pub fn answer(choice: Option<Choice>) -> Answer {
+    match choice {
+        Some(choice) => Answer::new(choice.answer())
+            .with("guard", choice.guard)
+            .with("minute", choice.minute),
+        None => Answer::new("nobody slept"),
+    }
+}
+
+/// The answer of `strategy` as a line of JSON. The puzzle's strategies answer
+/// their part of it, the others are keyed by their name instead.
+pub fn answer_json(strategy: &dyn Strategy, answer: &Answer, elapsed: Duration) -> String {
+    match strategy.part() {
+        Some(part) => output::to_json(4, part, answer, elapsed),
+        None => json!({
+            "day": 4,
+            "strategy": strategy.name(),
+            "answer": answer.value,
+            "elapsed": elapsed.as_secs_f64(),
+            "extra": answer.extra,
+        })
+        .to_string(),
+    }
+}
+
+// The example from the puzzle description, for the modules' tests.
+#[cfg(test)]
+const EXAMPLE: &[&str] = &[
+    "[1518-11-01 00:00] Guard #10 begins shift",
+    "[1518-11-01 00:05] falls asleep",
+    "[1518-11-01 00:25] wakes up",
+    "[1518-11-01 00:30] falls asleep",
+    "[1518-11-01 00:55] wakes up",
+    "[1518-11-01 23:58] Guard #99 begins shift",
+    "[1518-11-02 00:40] falls asleep",
+    "[1518-11-02 00:50] wakes up",
+    "[1518-11-03 00:05] Guard #10 begins shift",
+    "[1518-11-03 00:24] falls asleep",
+    "[1518-11-03 00:29] wakes up",
+    "[1518-11-04 00:02] Guard #99 begins shift",
+    "[1518-11-04 00:36] falls asleep",
+    "[1518-11-04 00:46] wakes up",
+    "[1518-11-05 00:03] Guard #99 begins shift",
+    "[1518-11-05 00:45] falls asleep",
+    "[1518-11-05 00:55] wakes up",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_parsing() {
+        let lines = [
+            "[1518-11-01 23:58] Guard #99 begins shift",
+            "[1518-11-02 00:40] falls asleep",
+            "[1518-11-02 00:50] wakes up",
+        ];
+
+        let parsed = parse_log_line(lines[0]).unwrap();
+        assert_eq!(parsed.log_type, LogType::ShiftStart(99));
+        assert_eq!(
+            parsed.time,
+            Utc.with_ymd_and_hms(1518, 11, 1, 23, 58, 00).unwrap()
+        );
+
+        let parsed = parse_log_line(lines[1]).unwrap();
+        assert_eq!(parsed.log_type, LogType::SleepStart);
+        assert_eq!(
+            parsed.time,
+            Utc.with_ymd_and_hms(1518, 11, 2, 00, 40, 00).unwrap()
+        );
+
+        let parsed = parse_log_line(lines[2]).unwrap();
+        assert_eq!(parsed.log_type, LogType::SleepEnd);
+        assert_eq!(
+            parsed.time,
+            Utc.with_ymd_and_hms(1518, 11, 2, 00, 50, 00).unwrap()
+        );
+    }
+
+    #[test]
+    fn multiple_lines_parsing_and_sorting() {
+        let lines = vec![
+            "[1518-10-15 00:42] wakes up",
+            "[1518-06-11 00:52] falls asleep",
+            "[1518-06-22 00:49] wakes up",
+            "[1518-06-27 23:58] Guard #2389 begins shift",
+            "[1518-10-14 00:11] falls asleep",
+            "[1518-03-28 23:59] Guard #1777 begins shift",
+            "[1518-06-16 00:46] falls asleep",
+        ];
+
+        let logs = parse_logs(lines).unwrap();
+        assert_eq!(logs.len(), 7);
+        assert_eq!(logs[0].log_type, LogType::ShiftStart(1777));
+        assert_eq!(logs[6].log_type, LogType::SleepEnd);
+    }
+
+    #[test]
+    fn converting_logs_to_guards() {
+        let lines = vec![
+            "[1518-03-27 00:03] Guard #2251 begins shift",
+            "[1518-03-27 00:11] falls asleep",
+            "[1518-03-27 00:57] wakes up",
+            "[1518-03-27 23:58] Guard #3319 begins shift",
+            "[1518-03-28 00:16] falls asleep",
+            "[1518-03-28 00:33] wakes up",
+            "[1518-03-28 00:53] falls asleep",
+            "[1518-03-28 00:54] wakes up",
+            "[1518-03-28 23:59] Guard #1777 begins shift",
+            "[1518-03-29 00:08] falls asleep",
+            "[1518-03-29 00:10] wakes up",
+            "[1518-03-29 00:20] falls asleep",
+            "[1518-03-29 00:55] wakes up",
+            "[1518-03-30 00:30] Guard #2251 begins shift",
+            "[1518-03-30 00:40] falls asleep",
+            "[1518-03-30 00:55] wakes up",
+        ];
+
+        let logs = parse_logs(lines).unwrap();
+        assert_eq!(logs.len(), 16);
+
+        let guards = logs_to_guards(logs);
+        let guard_1 = guards.get(&2251).unwrap();
+        assert!(guard_1.minutes_asleep[0..10].iter().all(|m| *m == 0));
+        assert!(guard_1.minutes_asleep[11..39].iter().all(|m| *m == 1));
+        assert!(guard_1.minutes_asleep[40..55].iter().all(|m| *m == 2));
+        assert!(guard_1.minutes_asleep[56..57].iter().all(|m| *m == 1));
+        assert!(guard_1.minutes_asleep[58..59].iter().all(|m| *m == 0));
+
+        let guard_2 = guards.get(&3319).unwrap();
+        assert!(guard_2.minutes_asleep[0..15].iter().all(|m| *m == 0));
+        assert!(guard_2.minutes_asleep[16..33].iter().all(|m| *m == 1));
+        assert!(guard_2.minutes_asleep[34..52].iter().all(|m| *m == 0));
+        assert!(guard_2.minutes_asleep[53..54].iter().all(|m| *m == 1));
+        assert!(guard_2.minutes_asleep[55..59].iter().all(|m| *m == 0));
+
+        let guard_3 = guards.get(&1777).unwrap();
+        assert!(guard_3.minutes_asleep[0..7].iter().all(|m| *m == 0));
+        assert!(guard_3.minutes_asleep[8..10].iter().all(|m| *m == 1));
+        assert!(guard_3.minutes_asleep[11..19].iter().all(|m| *m == 0));
+        assert!(guard_3.minutes_asleep[20..55].iter().all(|m| *m == 1));
+        assert!(guard_3.minutes_asleep[56..59].iter().all(|m| *m == 0));
+    }
+
+    #[test]
+    fn line_parsing_errors() {
+        let error = parse_log_line("[1518-11-01 23:58] Guard 99 begins shift").unwrap_err();
+        assert_eq!(error.column, 20);
+        assert_eq!(error.found, "Guard 99 begins shift");
+
+        let error = parse_log_line("[1518-02-30 00:40] falls asleep").unwrap_err();
+        assert_eq!(error.column, 2);
+        assert_eq!(error.found, "1518-02-30 00:40");
+        assert_eq!(error.expected, "a valid date and time");
+
+        let error = parse_log_line("1518-11-02 00:50 wakes up").unwrap_err();
+        assert_eq!(error.column, 1);
+
+        let lines = vec![
+            "[1518-11-02 00:40] falls asleep",
+            "[1518-11-02 00:50] wakes",
+        ];
+        let error = parse_logs(lines).unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn sleeping_across_midnight() {
+        let lines = vec![
+            "[1518-03-26 23:40] Guard #10 begins shift",
+            "[1518-03-26 23:55] falls asleep",
+            "[1518-03-27 00:05] wakes up",
+            "[1518-03-27 00:50] falls asleep",
+            "[1518-03-27 01:10] wakes up",
+        ];
+        let logs = parse_logs(lines).unwrap();
+        let window = Window::new(-60, 120).with_resolution(15);
+        assert!(validate(logs.clone(), window).is_empty());
+
+        let guards = logs_to_guards_within(logs.clone(), window);
+        let guard = &guards[&10];
+        assert_eq!(guard.naps.len(), 2);
+        assert_eq!(guard.naps[0].minutes(), 10);
+        assert_eq!(guard.sum_minutes_asleep(), 30);
+        // 23:45-00:00, 00:00-00:15, 00:45-01:00 and 01:00-01:15.
+        assert_eq!(
+            guard.minutes_asleep,
+            vec![0, 0, 0, 5, 5, 0, 0, 10, 10, 0, 0, 0]
+        );
+        assert_eq!(guard.highest_sleep(), 10);
+        assert_eq!(guard.sleepiest_minute(), 45);
+
+        // Only counting the midnight hour cuts both naps short.
+        let (repaired, _) = check_logs(logs, Mode::Lenient, Window::default()).unwrap();
+        let guards = logs_to_guards(repaired);
+        assert_eq!(guards[&10].sum_minutes_asleep(), 5 + 10);
+    }
+
+    #[test]
+    fn unchecked_logs_to_guards() {
+        let logs = parse_logs(vec![
+            "[1518-11-01 00:00] falls asleep",
+            "[1518-11-01 00:05] wakes up",
+            "[1518-11-01 23:58] Guard #99 begins shift",
+            "[1518-11-02 00:10] wakes up",
+            "[1518-11-02 00:40] falls asleep",
+            "[1518-11-02 00:50] wakes up",
+        ])
+        .unwrap();
+        let guards = logs_to_guards(logs);
+
+        assert_eq!(guards.len(), 1);
+        assert_eq!(guards[&99].naps.len(), 1);
+        assert_eq!(guards[&99].sum_minutes_asleep(), 10);
+    }
+
+    #[test]
+    fn answering_every_strategy_in_json() {
+        let logs = parse_logs(EXAMPLE.to_vec()).unwrap();
+        let guards: Vec<Guard> = logs_to_guards(logs).into_values().collect();
+
+        let lines: Vec<String> = strategies()
+            .iter()
+            .map(|s| {
+                answer_json(
+                    s.as_ref(),
+                    &answer(s.choose(&guards)),
+                    Duration::from_secs(0),
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"day":4,"part":1,"answer":"240","elapsed":0.0,"extra":{"guard":10,"minute":24}}"#,
+                r#"{"day":4,"part":2,"answer":"4455","elapsed":0.0,"extra":{"guard":99,"minute":45}}"#,
+                r#"{"day":4,"strategy":"longest-nap","answer":"300","elapsed":0.0,"extra":{"guard":10,"minute":30}}"#,
+                r#"{"day":4,"strategy":"most-nights","answer":"4455","elapsed":0.0,"extra":{"guard":99,"minute":45}}"#,
+                r#"{"day":4,"strategy":"earliest-sleeper","answer":"190","elapsed":0.0,"extra":{"guard":10,"minute":19}}"#,
+            ]
+        );
+    }
+}
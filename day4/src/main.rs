@@ -1,284 +1,274 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate chrono;
-extern crate regex;
-
-use chrono::prelude::*;
-use regex::Regex;
-use std::collections::HashMap;
+use chrono::NaiveDate;
+use common::input;
+use common::output::Format;
+use day4::*;
+use serde_json::json;
 use std::env;
-use std::fs::File;
-use std::io::prelude::*;
+use std::fs;
+use std::path::Path;
 use std::process;
-
-struct Guard {
-    id: u32,
-    minutes_asleep: [u32; 60],
+use std::time::Instant;
+
+// Options followed by a value.
+const OPTIONS: &[&str] = &[
+    "--window",
+    "--resolution",
+    "--strategy",
+    "--guard",
+    "--from",
+    "--to",
+    "--heatmap",
+    "--guards",
+    "--nights",
+];
+
+enum Command {
+    Solve,
+    /// `day4 stats`
+    Stats,
+    /// `day4 guard <id>`
+    Guard(u32),
 }
 
-impl Guard {
-    fn new(id: u32) -> Guard {
-        Guard {
-            id: id,
-            minutes_asleep: [0; 60],
+impl Command {
+    fn from_args() -> Command {
+        let args = input::positional(OPTIONS);
+        match args.first().map(String::as_str) {
+            Some("stats") => Command::Stats,
+            Some("guard") => match args.get(1).map(|id| id.trim_start_matches('#').parse()) {
+                Some(Ok(id)) => Command::Guard(id),
+                _ => {
+                    eprintln!("expected a guard id, like `day4 guard 10`");
+                    process::exit(1);
+                }
+            },
+            _ => Command::Solve,
         }
     }
 
-    fn sum_minutes_asleep(&self) -> u32 {
-        self.minutes_asleep.iter().fold(0, |a, &b| a + b)
-    }
-
-    fn sleepiest_minute(&self) -> usize {
-        let mut i = 0;
-
-        for (j, &value) in self.minutes_asleep.iter().enumerate() {
-            if value > self.minutes_asleep[i] {
-                i = j;
-            }
+    // How many arguments come before the name of the input.
+    fn arguments(&self) -> usize {
+        match self {
+            Command::Solve => 0,
+            Command::Stats => 1,
+            Command::Guard(_) => 2,
         }
-
-        i
-    }
-
-    fn highest_sleep(&self) -> u32 {
-        *self.minutes_asleep.iter().max_by_key(|x| *x).unwrap()
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum LogType {
-    SleepStart,
-    SleepEnd,
-    ShiftStart(u32),
-}
-
-struct Log {
-    log_type: LogType,
-    time: DateTime<UTC>,
-}
-
-fn parse_log_line(line: &str) -> Option<Log> {
-    lazy_static! {
-        static ref LINE_RE: Regex =
-            Regex::new(r"\[(\d+)-(\d+)-(\d+)\s(\d+):(\d+)\]\s(.*)").unwrap();
-        static ref SHIFT_START_RE: Regex = Regex::new(r"Guard #(\d+) begins shift").unwrap();
-    }
-
-    let caps = LINE_RE.captures(line)?;
+fn main() -> Result<(), std::io::Error> {
+    let command = Command::from_args();
+    let input = input::from_args_after(env!("CARGO_MANIFEST_DIR"), OPTIONS, command.arguments())
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        });
 
-    let year = caps.get(1)?.as_str().parse::<i32>().unwrap();
-    let month = caps.get(2)?.as_str().parse::<u32>().unwrap();
-    let day = caps.get(3)?.as_str().parse::<u32>().unwrap();
-    let hour = caps.get(4)?.as_str().parse::<u32>().unwrap();
-    let minute = caps.get(5)?.as_str().parse::<u32>().unwrap();
-    let message = caps.get(6)?.as_str();
+    let logs = parse_logs(input.contents.lines()).unwrap_or_else(|e| input.exit_with(&e));
 
-    let time = UTC.ymd(year, month, day).and_hms(hour, minute, 00);
-    let log_type = match message {
-        "falls asleep" => LogType::SleepStart,
-        "wakes up" => LogType::SleepEnd,
-        shift_start => {
-            let caps = SHIFT_START_RE.captures(shift_start)?;
-            let guard_id = caps.get(1)?.as_str().parse::<u32>().unwrap();
-            LogType::ShiftStart(guard_id)
-        }
+    let mut window = match input::option("--window") {
+        Some(window) => window.parse::<Window>().unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        }),
+        None => Window::default(),
     };
-
-    Some(Log { log_type, time })
-}
-
-fn parse_logs<'a, I>(lines: I) -> Vec<Log>
-where
-    I: IntoIterator<Item = &'a str>,
-{
-    let mut parsed = Vec::new();
-
-    for (_, line) in lines.into_iter().enumerate() {
-        if let Some(parsed_line) = parse_log_line(line) {
-            parsed.push(parsed_line);
+    if let Some(resolution) = input::option("--resolution") {
+        match resolution.parse::<i64>() {
+            Ok(resolution) if resolution > 0 => window = window.with_resolution(resolution),
+            _ => {
+                eprintln!("invalid resolution `{}`, expected minutes", resolution);
+                process::exit(1);
+            }
         }
     }
 
-    parsed.sort_unstable_by_key(|k| k.time);
-    parsed
-}
-
-fn logs_to_guards(logs: Vec<Log>) -> HashMap<u32, Guard> {
-    let mut guards = HashMap::new();
-
-    let mut current_id = 0;
-    let mut sleep_start = 0;
-
-    for (_, log) in logs.iter().enumerate() {
-        match log.log_type {
-            LogType::ShiftStart(id) => {
-                guards.entry(id).or_insert_with(|| Guard::new(id));
-                current_id = id;
+    // Without `--strict`, problems in the logs are repaired and only reported.
+    let mode = if env::args().any(|arg| arg == "--strict") {
+        Mode::Strict
+    } else {
+        Mode::Lenient
+    };
+    let logs = match check_logs(logs, mode, window) {
+        Ok((logs, diagnostics)) => {
+            for diagnostic in &diagnostics {
+                eprintln!("warning: {}", diagnostic);
             }
-            LogType::SleepStart => {
-                sleep_start = log.time.minute() as usize;
+            logs
+        }
+        Err(diagnostics) => {
+            for diagnostic in &diagnostics {
+                eprintln!("error: {}", diagnostic);
             }
-            LogType::SleepEnd => {
-                let sleep_end = log.time.minute() as usize;
-
-                let mut guard = guards.get_mut(&current_id).unwrap();
+            eprintln!("{} problem(s) in the guard logs", diagnostics.len());
+            process::exit(1);
+        }
+    };
 
-                for minute in sleep_start..sleep_end {
-                    guard.minutes_asleep[minute] += 1;
-                }
+    let guards: Vec<Guard> = logs_to_guards_within(logs.clone(), window)
+        .into_values()
+        .collect();
+
+    let chosen = chosen_strategies();
+
+    // Answers from the checked logs, the configured window and the chosen
+    // strategies, so that all of the options hold for JSON too.
+    if let Command::Solve = command {
+        if Format::from_args() == Format::Json {
+            for strategy in &chosen {
+                let start = Instant::now();
+                let answer = answer(strategy.choose(&guards));
+                println!(
+                    "{}",
+                    answer_json(strategy.as_ref(), &answer, start.elapsed())
+                );
             }
+            return Ok(());
         }
     }
-
-    guards
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn line_parsing() {
-        let lines = vec![
-            "[1518-11-01 23:58] Guard #99 begins shift",
-            "[1518-11-02 00:40] falls asleep",
-            "[1518-11-02 00:50] wakes up",
-        ];
-
-        let parsed = parse_log_line(lines[0]).unwrap();
-        assert_eq!(parsed.log_type, LogType::ShiftStart(99));
-        assert_eq!(parsed.time, UTC.ymd(1518, 11, 01).and_hms(23, 58, 00));
-
-        let parsed = parse_log_line(lines[1]).unwrap();
-        assert_eq!(parsed.log_type, LogType::SleepStart);
-        assert_eq!(parsed.time, UTC.ymd(1518, 11, 02).and_hms(00, 40, 00));
-
-        let parsed = parse_log_line(lines[2]).unwrap();
-        assert_eq!(parsed.log_type, LogType::SleepEnd);
-        assert_eq!(parsed.time, UTC.ymd(1518, 11, 02).and_hms(00, 50, 00));
+    match command {
+        Command::Solve => {}
+        Command::Stats => return print_stats(&guards),
+        Command::Guard(id) => return print_guard(&guards, &logs, window, id),
     }
 
-    #[test]
-    fn multiple_lines_parsing_and_sorting() {
-        let lines = vec![
-            "[1518-10-15 00:42] wakes up",
-            "[1518-06-11 00:52] falls asleep",
-            "[1518-06-22 00:49] wakes up",
-            "[1518-06-27 23:58] Guard #2389 begins shift",
-            "[1518-10-14 00:11] falls asleep",
-            "[1518-03-28 23:59] Guard #1777 begins shift",
-            "[1518-06-16 00:46] falls asleep",
-        ];
+    let date = |option| {
+        input::option(option).map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap_or_else(|_| {
+                eprintln!("invalid date `{}`, expected one like `1518-11-01`", date);
+                process::exit(1);
+            })
+        })
+    };
+    let filter = Filter {
+        guard: input::option("--guard").map(|id| {
+            id.trim_start_matches('#').parse().unwrap_or_else(|_| {
+                eprintln!("invalid guard `{}`, expected an id", id);
+                process::exit(1);
+            })
+        }),
+        from: date("--from"),
+        to: date("--to"),
+    };
+    let shown: Vec<Night> = nights(&logs, window)
+        .into_iter()
+        .filter(|night| filter.matches(night))
+        .collect();
 
-        let logs = parse_logs(lines);
-        assert_eq!(logs.len(), 7);
-        assert_eq!(logs[0].log_type, LogType::ShiftStart(1777));
-        assert_eq!(logs[6].log_type, LogType::SleepEnd);
+    print!("{}", chart(&shown, window));
+    if let Some(path) = input::option("--heatmap") {
+        fs::write(&path, heatmap(&shown, window))?;
+        println!("heatmap of {} night(s) written to {}", shown.len(), path);
     }
 
-    #[test]
-    fn converting_logs_to_guards() {
-        let lines = vec![
-            "[1518-03-27 00:03] Guard #2251 begins shift",
-            "[1518-03-27 00:11] falls asleep",
-            "[1518-03-27 00:57] wakes up",
-            "[1518-03-27 23:58] Guard #3319 begins shift",
-            "[1518-03-28 00:16] falls asleep",
-            "[1518-03-28 00:33] wakes up",
-            "[1518-03-28 00:53] falls asleep",
-            "[1518-03-28 00:54] wakes up",
-            "[1518-03-28 23:59] Guard #1777 begins shift",
-            "[1518-03-29 00:08] falls asleep",
-            "[1518-03-29 00:10] wakes up",
-            "[1518-03-29 00:20] falls asleep",
-            "[1518-03-29 00:55] wakes up",
-            "[1518-03-30 00:30] Guard #2251 begins shift",
-            "[1518-03-30 00:40] falls asleep",
-            "[1518-03-30 00:55] wakes up",
-        ];
-
-        let logs = parse_logs(lines);
-        assert_eq!(logs.len(), 16);
-
-        let guards = logs_to_guards(logs);
-        let guard_1 = guards.get(&2251).unwrap();
-        assert!(guard_1.minutes_asleep[0..10].iter().all(|m| *m == 0));
-        assert!(guard_1.minutes_asleep[11..39].iter().all(|m| *m == 1));
-        assert!(guard_1.minutes_asleep[40..55].iter().all(|m| *m == 2));
-        assert!(guard_1.minutes_asleep[56..57].iter().all(|m| *m == 1));
-        assert!(guard_1.minutes_asleep[58..59].iter().all(|m| *m == 0));
-
-        let guard_2 = guards.get(&3319).unwrap();
-        assert!(guard_2.minutes_asleep[0..15].iter().all(|m| *m == 0));
-        assert!(guard_2.minutes_asleep[16..33].iter().all(|m| *m == 1));
-        assert!(guard_2.minutes_asleep[34..52].iter().all(|m| *m == 0));
-        assert!(guard_2.minutes_asleep[53..54].iter().all(|m| *m == 1));
-        assert!(guard_2.minutes_asleep[55..59].iter().all(|m| *m == 0));
-
-        let guard_3 = guards.get(&1777).unwrap();
-        assert!(guard_3.minutes_asleep[0..7].iter().all(|m| *m == 0));
-        assert!(guard_3.minutes_asleep[08..10].iter().all(|m| *m == 1));
-        assert!(guard_3.minutes_asleep[11..19].iter().all(|m| *m == 0));
-        assert!(guard_3.minutes_asleep[20..55].iter().all(|m| *m == 1));
-        assert!(guard_3.minutes_asleep[56..59].iter().all(|m| *m == 0));
+    for strategy in chosen {
+        match strategy.choose(&guards) {
+            Some(choice) => println!(
+                "{} ({}): guard #{} at minute {}, scoring {}. result: {}",
+                strategy.name(),
+                strategy.description(),
+                choice.guard,
+                choice.minute,
+                choice.score,
+                choice.answer()
+            ),
+            None => println!("{}: nobody fell asleep", strategy.name()),
+        }
     }
+    Ok(())
 }
 
-fn main() -> Result<(), std::io::Error> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 {
-        eprintln!("not enough arguments");
-        process::exit(1);
+// The puzzle's two strategies by default, `--strategy all` for all.
+fn chosen_strategies() -> Vec<Box<dyn Strategy>> {
+    match input::option("--strategy").as_deref() {
+        None => strategies().into_iter().take(2).collect(),
+        Some("all") => strategies(),
+        Some(name) => match strategy(name) {
+            Some(strategy) => vec![strategy],
+            None => {
+                let names: Vec<&str> = strategies().iter().map(|s| s.name()).collect();
+                eprintln!(
+                    "unknown strategy `{}`, expected one of {} or `all`",
+                    name,
+                    names.join(", ")
+                );
+                process::exit(1);
+            }
+        },
     }
+}
 
-    let filename = args[1].clone();
-    let mut f = File::open(filename)?;
-    let mut contents = String::new();
-    f.read_to_string(&mut contents)?;
+// Writes the rows to the files given with `--guards` and `--nights`, or
+// prints them all without either.
+fn print_stats(guards: &[Guard]) -> Result<(), std::io::Error> {
+    let (guard_rows, night_rows) = (guard_stats(guards), night_stats(guards));
+    let (guards_path, nights_path) = (input::option("--guards"), input::option("--nights"));
+
+    if guards_path.is_none() && nights_path.is_none() {
+        match Format::from_args() {
+            Format::Json => println!(
+                "{}",
+                json!({ "guards": to_json(&guard_rows), "nights": to_json(&night_rows) })
+            ),
+            Format::Text => print!("{}\n{}", to_csv(&guard_rows), to_csv(&night_rows)),
+        }
+        return Ok(());
+    }
 
-    let logs = parse_logs(contents.lines());
+    if let Some(path) = guards_path {
+        save(&guard_rows, Path::new(&path))?;
+        println!("{} guard(s) written to {}", guard_rows.len(), path);
+    }
+    if let Some(path) = nights_path {
+        save(&night_rows, Path::new(&path))?;
+        println!("{} night(s) written to {}", night_rows.len(), path);
+    }
+    Ok(())
+}
 
-    let mut guards = logs_to_guards(logs)
-        .into_iter()
-        .fold(Vec::new(), |mut acc, kv| {
-            let (_, guard) = kv;
-            acc.push(guard);
-            acc
-        });
+fn print_guard(
+    guards: &[Guard],
+    logs: &[Log],
+    window: Window,
+    id: u32,
+) -> Result<(), std::io::Error> {
+    let guard = match guards.iter().find(|guard| guard.id == id) {
+        Some(guard) => guard,
+        None => {
+            eprintln!("there's no guard #{} in the logs", id);
+            process::exit(1);
+        }
+    };
+    let stats = guard_stats(std::slice::from_ref(guard)).remove(0);
+    let night_rows = night_stats(std::slice::from_ref(guard));
 
-    for (_, guard) in guards.iter().enumerate() {
+    if Format::from_args() == Format::Json {
+        let mut row = to_json(&[stats]);
         println!(
-            "guard #{}.\tminutes_asleep: {:?}",
-            guard.id,
-            &guard.minutes_asleep[..]
+            "{}",
+            json!({ "guard": row[0].take(), "nights": to_json(&night_rows) })
         );
+        return Ok(());
     }
 
-    guards.sort_unstable_by_key(|g| g.sum_minutes_asleep());
-    let slept_the_most = guards.last().unwrap();
-    println!(
-        "guard {} slept the most with {} minutes",
-        slept_the_most.id,
-        slept_the_most.sum_minutes_asleep()
-    );
-
     println!(
-        "sleepiest minute for guard {} was minute {}. result for part 1: {}",
-        slept_the_most.id,
-        slept_the_most.sleepiest_minute(),
-        slept_the_most.id * slept_the_most.sleepiest_minute() as u32
-    );
-
-    guards.sort_unstable_by_key(|g| g.highest_sleep());
-    let has_sleepiest_minute = guards.last().unwrap();
-
-    println!(
-        "guard {} has sleepiest minute {} with {} overlaps. result for part 2: {}",
-        has_sleepiest_minute.id,
-        has_sleepiest_minute.sleepiest_minute(),
-        has_sleepiest_minute.highest_sleep(),
-        has_sleepiest_minute.id * has_sleepiest_minute.sleepiest_minute() as u32
+        "guard #{}: {} minute(s) asleep in {} nap(s) over {} shift(s)",
+        stats.id, stats.total_minutes, stats.naps, stats.shifts
     );
+    if let Some(minute) = stats.sleepiest_minute {
+        println!(
+            "asleep most often at minute {}, on {} night(s)",
+            minute, stats.peak_count
+        );
+    }
+    let filter = Filter {
+        guard: Some(id),
+        ..Filter::default()
+    };
+    let shown: Vec<Night> = nights(logs, window)
+        .into_iter()
+        .filter(|night| filter.matches(night))
+        .collect();
+    print!("{}", chart(&shown, window));
     Ok(())
 }
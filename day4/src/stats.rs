@@ -0,0 +1,226 @@
+use crate::{midnight, Guard};
+use chrono::prelude::*;
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A row of a table that can be written as CSV or JSON.
+pub trait Row {
+    const COLUMNS: &'static [&'static str];
+
+    /// One value per column, `null` for the empty ones.
+    fn values(&self) -> Vec<Value>;
+}
+
+/// What a guard did over all of their shifts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GuardStats {
+    pub id: u32,
+    pub total_minutes: u32,
+    /// `None` if the guard never slept during the window.
+    pub sleepiest_minute: Option<i64>,
+    /// How often the guard was asleep at the sleepiest minute.
+    pub peak_count: u32,
+    pub shifts: usize,
+    pub naps: usize,
+}
+
+impl Row for GuardStats {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "total_minutes",
+        "sleepiest_minute",
+        "peak_count",
+        "shifts",
+        "naps",
+    ];
+
+    fn values(&self) -> Vec<Value> {
+        vec![
+            self.id.into(),
+            self.total_minutes.into(),
+            self.sleepiest_minute.into(),
+            self.peak_count.into(),
+            self.shifts.into(),
+            self.naps.into(),
+        ]
+    }
+}
+
+/// What a guard did during one shift. The minutes are relative to midnight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NightStats {
+    pub date: NaiveDate,
+    pub guard: u32,
+    pub naps: usize,
+    pub minutes_asleep: i64,
+    pub fell_asleep: Option<i64>,
+    pub woke_up: Option<i64>,
+}
+
+impl Row for NightStats {
+    const COLUMNS: &'static [&'static str] = &[
+        "date",
+        "guard",
+        "naps",
+        "minutes_asleep",
+        "fell_asleep",
+        "woke_up",
+    ];
+
+    fn values(&self) -> Vec<Value> {
+        vec![
+            self.date.to_string().into(),
+            self.guard.into(),
+            self.naps.into(),
+            self.minutes_asleep.into(),
+            self.fell_asleep.into(),
+            self.woke_up.into(),
+        ]
+    }
+}
+
+/// A row per guard, ordered by id.
+pub fn guard_stats(guards: &[Guard]) -> Vec<GuardStats> {
+    let mut stats: Vec<GuardStats> = guards
+        .iter()
+        .map(|guard| {
+            let peak_count = guard.highest_sleep();
+            GuardStats {
+                id: guard.id,
+                total_minutes: guard.sum_minutes_asleep(),
+                sleepiest_minute: Some(guard.sleepiest_minute()).filter(|_| peak_count > 0),
+                peak_count,
+                shifts: guard.shifts.len(),
+                naps: guard.naps.len(),
+            }
+        })
+        .collect();
+    stats.sort_by_key(|row| row.id);
+    stats
+}
+
+/// A row per shift, ordered by date and then guard.
+pub fn night_stats(guards: &[Guard]) -> Vec<NightStats> {
+    let mut stats = Vec::new();
+
+    for guard in guards {
+        let mut nights: Vec<DateTime<Utc>> = guard.shifts.iter().map(|&t| midnight(t)).collect();
+        nights.dedup();
+
+        for night in nights {
+            let naps: Vec<_> = guard
+                .naps
+                .iter()
+                .filter(|nap| midnight(nap.start) == night)
+                .collect();
+            stats.push(NightStats {
+                date: night.date_naive(),
+                guard: guard.id,
+                naps: naps.len(),
+                minutes_asleep: naps.iter().map(|nap| nap.minutes()).sum(),
+                fell_asleep: naps.first().map(|nap| (nap.start - night).num_minutes()),
+                woke_up: naps.last().map(|nap| (nap.end - night).num_minutes()),
+            });
+        }
+    }
+
+    stats.sort_by_key(|row| (row.date, row.guard));
+    stats
+}
+
+/// The rows with a header line. None of the values need quoting.
+pub fn to_csv<R: Row>(rows: &[R]) -> String {
+    let mut csv = R::COLUMNS.join(",");
+    csv.push('\n');
+
+    for row in rows {
+        let values: Vec<String> = row
+            .values()
+            .into_iter()
+            .map(|value| match value {
+                Value::Null => String::new(),
+                Value::String(s) => s,
+                value => value.to_string(),
+            })
+            .collect();
+        csv.push_str(&values.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// The rows as an array of objects, keyed by column.
+pub fn to_json<R: Row>(rows: &[R]) -> Value {
+    rows.iter()
+        .map(|row| {
+            let object: Map<String, Value> = R::COLUMNS
+                .iter()
+                .map(|column| column.to_string())
+                .zip(row.values())
+                .collect();
+            Value::Object(object)
+        })
+        .collect()
+}
+
+/// Writes the rows as JSON if `path` ends in `.json`, as CSV otherwise.
+pub fn save<R: Row>(rows: &[R], path: &Path) -> io::Result<()> {
+    let contents = match path.extension() {
+        Some(extension) if extension == "json" => {
+            let mut json = serde_json::to_string_pretty(&to_json(rows)).unwrap();
+            json.push('\n');
+            json
+        }
+        _ => to_csv(rows),
+    };
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{logs_to_guards, parse_logs, EXAMPLE};
+
+    #[test]
+    fn stats_of_guards_and_nights() {
+        let logs = parse_logs(EXAMPLE.to_vec()).unwrap();
+        let guards: Vec<Guard> = logs_to_guards(logs).into_values().collect();
+
+        let rows = guard_stats(&guards);
+        assert_eq!(
+            rows[0],
+            GuardStats {
+                id: 10,
+                total_minutes: 50,
+                sleepiest_minute: Some(24),
+                peak_count: 2,
+                shifts: 2,
+                naps: 3,
+            }
+        );
+        assert_eq!(
+            to_csv(&rows),
+            "id,total_minutes,sleepiest_minute,peak_count,shifts,naps\n10,50,24,2,2,3\n99,30,45,3,3,3\n"
+        );
+
+        let nights = night_stats(&guards);
+        assert_eq!(nights.len(), 5);
+        assert_eq!(
+            nights[1],
+            NightStats {
+                date: NaiveDate::from_ymd_opt(1518, 11, 2).unwrap(),
+                guard: 99,
+                naps: 1,
+                minutes_asleep: 10,
+                fell_asleep: Some(40),
+                woke_up: Some(50),
+            }
+        );
+        assert_eq!(
+            to_json(&nights[..1]).to_string(),
+            r#"[{"date":"1518-11-01","guard":10,"naps":2,"minutes_asleep":45,"fell_asleep":5,"woke_up":55}]"#
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{midnight, Guard};
+use std::collections::HashSet;
+
+/// The guard a strategy picked, the minute to sneak in at and how the guard
+/// scored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Choice {
+    pub guard: u32,
+    /// Relative to midnight.
+    pub minute: i64,
+    pub score: i64,
+}
+
+impl Choice {
+    /// What the puzzle asks for: the guard's id times the minute.
+    pub fn answer(&self) -> i64 {
+        i64::from(self.guard) * self.minute
+    }
+}
+
+/// A way of picking the guard to sneak past.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+
+    /// The part of the puzzle the strategy answers, if it's one of the
+    /// puzzle's.
+    fn part(&self) -> Option<u32> {
+        None
+    }
+
+    /// `None` if none of the guards ever fell asleep.
+    fn choose(&self, guards: &[Guard]) -> Option<Choice>;
+}
+
+// The guard with the highest score that ever slept, choosing the lowest id
+// of equally sleepy guards.
+fn highest<F: Fn(&Guard) -> i64>(guards: &[Guard], score: F) -> Option<(&Guard, i64)> {
+    guards
+        .iter()
+        .filter(|g| !g.naps.is_empty())
+        .map(|g| (g, score(g)))
+        .max_by_key(|(g, score)| (*score, std::cmp::Reverse(g.id)))
+}
+
+// The minute after midnight a nap started at.
+fn start_minute(guard: &Guard, nap: usize) -> i64 {
+    let start = guard.naps[nap].start;
+    (start - midnight(start)).num_minutes()
+}
+
+/// Strategy 1 of the puzzle: the guard that slept the most minutes, at the
+/// minute they're asleep most often.
+pub struct MostMinutes;
+
+impl Strategy for MostMinutes {
+    fn name(&self) -> &'static str {
+        "most-minutes"
+    }
+
+    fn description(&self) -> &'static str {
+        "the guard asleep for the most minutes"
+    }
+
+    fn part(&self) -> Option<u32> {
+        Some(1)
+    }
+
+    fn choose(&self, guards: &[Guard]) -> Option<Choice> {
+        let (guard, score) = highest(guards, |g| i64::from(g.sum_minutes_asleep()))?;
+        Some(Choice {
+            guard: guard.id,
+            minute: guard.sleepiest_minute(),
+            score,
+        })
+    }
+}
+
+/// Strategy 2 of the puzzle: the guard that's asleep most often on the same
+/// minute.
+pub struct SameMinute;
+
+impl Strategy for SameMinute {
+    fn name(&self) -> &'static str {
+        "same-minute"
+    }
+
+    fn description(&self) -> &'static str {
+        "the guard asleep most often on the same minute"
+    }
+
+    fn part(&self) -> Option<u32> {
+        Some(2)
+    }
+
+    fn choose(&self, guards: &[Guard]) -> Option<Choice> {
+        let (guard, score) = highest(guards, |g| i64::from(g.highest_sleep()))?;
+        Some(Choice {
+            guard: guard.id,
+            minute: guard.sleepiest_minute(),
+            score,
+        })
+    }
+}
+
+/// The guard that took the longest nap, at the minute that nap started.
+pub struct LongestNap;
+
+impl Strategy for LongestNap {
+    fn name(&self) -> &'static str {
+        "longest-nap"
+    }
+
+    fn description(&self) -> &'static str {
+        "the guard that took the longest nap, when it started"
+    }
+
+    fn choose(&self, guards: &[Guard]) -> Option<Choice> {
+        let longest = |g: &Guard| g.naps.iter().map(|nap| nap.minutes()).max().unwrap_or(0);
+        let (guard, score) = highest(guards, longest)?;
+
+        // The first of the longest naps.
+        let nap = guard
+            .naps
+            .iter()
+            .position(|nap| nap.minutes() == score)
+            .unwrap();
+        Some(Choice {
+            guard: guard.id,
+            minute: start_minute(guard, nap),
+            score,
+        })
+    }
+}
+
+/// The guard that fell asleep on the most nights, at their sleepiest minute.
+pub struct MostNights;
+
+impl Strategy for MostNights {
+    fn name(&self) -> &'static str {
+        "most-nights"
+    }
+
+    fn description(&self) -> &'static str {
+        "the guard that fell asleep on the most nights"
+    }
+
+    fn choose(&self, guards: &[Guard]) -> Option<Choice> {
+        let nights = |g: &Guard| {
+            let nights: HashSet<_> = g.naps.iter().map(|nap| midnight(nap.start)).collect();
+            nights.len() as i64
+        };
+        let (guard, score) = highest(guards, nights)?;
+        Some(Choice {
+            guard: guard.id,
+            minute: guard.sleepiest_minute(),
+            score,
+        })
+    }
+}
+
+/// The guard that falls asleep earliest on average, at that average minute.
+/// The score is the average minute too, so lower is sleepier here.
+pub struct EarliestSleeper;
+
+impl Strategy for EarliestSleeper {
+    fn name(&self) -> &'static str {
+        "earliest-sleeper"
+    }
+
+    fn description(&self) -> &'static str {
+        "the guard falling asleep earliest on average, at that minute"
+    }
+
+    fn choose(&self, guards: &[Guard]) -> Option<Choice> {
+        let average = |g: &Guard| {
+            let sum: i64 = (0..g.naps.len()).map(|nap| start_minute(g, nap)).sum();
+            sum.div_euclid(g.naps.len().max(1) as i64)
+        };
+        let (guard, score) = highest(guards, |g| -average(g))?;
+        Some(Choice {
+            guard: guard.id,
+            minute: -score,
+            score: -score,
+        })
+    }
+}
+
+/// Every strategy there is, the puzzle's two first.
+pub fn strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(MostMinutes),
+        Box::new(SameMinute),
+        Box::new(LongestNap),
+        Box::new(MostNights),
+        Box::new(EarliestSleeper),
+    ]
+}
+
+/// The strategy called `name`. The puzzle's are also called `1` and `2`.
+pub fn strategy(name: &str) -> Option<Box<dyn Strategy>> {
+    let name = match name {
+        "1" => "most-minutes",
+        "2" => "same-minute",
+        name => name,
+    };
+    strategies().into_iter().find(|s| s.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{logs_to_guards, parse_logs};
+
+    #[test]
+    fn choosing_guards() {
+        let lines = vec![
+            "[1518-03-27 00:03] Guard #2251 begins shift",
+            "[1518-03-27 00:11] falls asleep",
+            "[1518-03-27 00:57] wakes up",
+            "[1518-03-27 23:58] Guard #3319 begins shift",
+            "[1518-03-28 00:16] falls asleep",
+            "[1518-03-28 00:33] wakes up",
+            "[1518-03-28 00:53] falls asleep",
+            "[1518-03-28 00:54] wakes up",
+            "[1518-03-28 23:59] Guard #1777 begins shift",
+            "[1518-03-29 00:08] falls asleep",
+            "[1518-03-29 00:10] wakes up",
+            "[1518-03-29 00:20] falls asleep",
+            "[1518-03-29 00:55] wakes up",
+            "[1518-03-30 00:30] Guard #2251 begins shift",
+            "[1518-03-30 00:40] falls asleep",
+            "[1518-03-30 00:55] wakes up",
+        ];
+        let logs = parse_logs(lines).unwrap();
+        let guards: Vec<Guard> = logs_to_guards(logs).into_values().collect();
+
+        let choose = |name: &str| strategy(name).unwrap().choose(&guards).unwrap();
+        let choice = |guard, minute, score| Choice {
+            guard,
+            minute,
+            score,
+        };
+
+        // 2251 slept 46 + 15 minutes, twice during minutes 40 to 54.
+        assert_eq!(choose("most-minutes"), choice(2251, 40, 61));
+        assert_eq!(choose("1").answer(), 2251 * 40);
+        assert_eq!(choose("same-minute"), choice(2251, 40, 2));
+        assert_eq!(choose("longest-nap"), choice(2251, 11, 46));
+        assert_eq!(choose("most-nights"), choice(2251, 40, 2));
+        // 1777 fell asleep at 00:08 and 00:20.
+        assert_eq!(choose("earliest-sleeper"), choice(1777, 14, 14));
+
+        assert!(strategy("3").is_none());
+        assert_eq!(MostMinutes.choose(&[]), None);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{Log, LogType, Window};
+use chrono::prelude::*;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Problem {
+    /// Someone fell asleep before any guard began a shift.
+    NoShift,
+    /// A guard woke up without having fallen asleep.
+    OrphanWake,
+    /// A guard fell asleep while already asleep.
+    DoubleSleep,
+    /// A shift, or the whole log, ended while the guard was still asleep.
+    UnclosedSleep,
+    /// A guard fell asleep or woke up outside of the window watched, which
+    /// is the midnight hour unless configured otherwise.
+    OutsideWindow(Window),
+}
+
+/// A problem with the log entry at `time`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub problem: Problem,
+    pub time: DateTime<Utc>,
+    pub guard: Option<u32>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] ", self.time.format("%Y-%m-%d %H:%M"))?;
+        match self.guard {
+            Some(id) => write!(f, "guard #{} ", id)?,
+            None => write!(f, "someone ")?,
+        }
+        match self.problem {
+            Problem::NoShift => write!(f, "fell asleep before any guard began a shift"),
+            Problem::OrphanWake => write!(f, "woke up without having fallen asleep"),
+            Problem::DoubleSleep => write!(f, "fell asleep while already asleep"),
+            Problem::UnclosedSleep => write!(f, "was still asleep when the shift ended"),
+            Problem::OutsideWindow(window) => write!(f, "was asleep outside of {}", window),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Any problem fails the check.
+    Strict,
+    /// Problems are repaired as well as possible and only reported.
+    Lenient,
+}
+
+// The guard that's asleep, and since when.
+struct Asleep {
+    guard: u32,
+    start: DateTime<Utc>,
+}
+
+// Keeps a nap once it's over, but only if any of it is left.
+fn keep_nap(repaired: &mut Vec<Log>, start: DateTime<Utc>, end: DateTime<Utc>) {
+    if end > start {
+        repaired.push(Log {
+            log_type: LogType::SleepStart,
+            time: start,
+        });
+        repaired.push(Log {
+            log_type: LogType::SleepEnd,
+            time: end,
+        });
+    }
+}
+
+// Walks through the sorted logs like `logs_to_guards` does, returning the
+// logs with every problem repaired:
+//
+// - sleeping before any shift, waking up without sleeping and falling asleep
+//   again are dropped,
+// - sleeping into the next shift or the end of the log gets a wake up at
+//   the next log entry or the end of the window, whichever comes first,
+// - sleeping or waking up outside of the window is moved into it, and naps
+//   that are entirely outside of it are dropped.
+fn walk(logs: Vec<Log>, window: Window, diagnostics: &mut Vec<Diagnostic>) -> Vec<Log> {
+    let mut repaired = Vec::new();
+    let mut guard = None;
+    let mut nap: Option<Asleep> = None;
+    // Whether the guard fell asleep outside of the window, so that waking
+    // up again is dropped along with it.
+    let mut dropped = false;
+
+    let mut report = |problem, time, guard| {
+        diagnostics.push(Diagnostic {
+            problem,
+            time,
+            guard,
+        })
+    };
+    for log in logs {
+        match log.log_type {
+            LogType::ShiftStart(id) => {
+                if let Some(nap) = nap.take() {
+                    report(Problem::UnclosedSleep, nap.start, Some(nap.guard));
+                    keep_nap(
+                        &mut repaired,
+                        nap.start,
+                        log.time.min(window.around(nap.start).1),
+                    );
+                }
+                guard = Some(id);
+                dropped = false;
+                repaired.push(log);
+            }
+            LogType::SleepStart => {
+                let id = match guard {
+                    Some(id) => id,
+                    None => {
+                        report(Problem::NoShift, log.time, None);
+                        continue;
+                    }
+                };
+                if nap.is_some() {
+                    report(Problem::DoubleSleep, log.time, Some(id));
+                    continue;
+                }
+
+                let (opens, closes) = window.around(log.time);
+                let start = log.time.max(opens).min(closes);
+                if start != log.time {
+                    report(Problem::OutsideWindow(window), log.time, Some(id));
+                }
+                if start == closes {
+                    dropped = true;
+                    continue;
+                }
+                nap = Some(Asleep { guard: id, start });
+            }
+            LogType::SleepEnd => {
+                let start = match nap.take() {
+                    Some(nap) => nap.start,
+                    None if dropped => {
+                        dropped = false;
+                        continue;
+                    }
+                    None => {
+                        report(Problem::OrphanWake, log.time, guard);
+                        continue;
+                    }
+                };
+
+                // The guard fell asleep within the window, so waking up
+                // before that did was outside of it too.
+                let end = log.time.max(start).min(window.around(start).1);
+                if end != log.time {
+                    report(Problem::OutsideWindow(window), log.time, guard);
+                }
+                keep_nap(&mut repaired, start, end);
+            }
+        }
+    }
+
+    if let Some(nap) = nap {
+        report(Problem::UnclosedSleep, nap.start, Some(nap.guard));
+        keep_nap(&mut repaired, nap.start, window.around(nap.start).1);
+    }
+
+    repaired
+}
+
+/// Every problem in the sorted `logs`, in order.
+pub fn validate(logs: Vec<Log>, window: Window) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    walk(logs, window, &mut diagnostics);
+    diagnostics
+}
+
+/// In strict mode, returns the logs unchanged if there's nothing wrong with
+/// them and all problems otherwise. In lenient mode, returns the repaired
+/// logs together with the problems.
+pub fn check_logs(
+    logs: Vec<Log>,
+    mode: Mode,
+    window: Window,
+) -> Result<(Vec<Log>, Vec<Diagnostic>), Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let repaired = walk(logs, window, &mut diagnostics);
+
+    if mode == Mode::Strict && !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+    Ok((repaired, diagnostics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{logs_to_guards, parse_log_line, parse_logs};
+
+    fn log(line: &str) -> Log {
+        parse_log_line(line).unwrap()
+    }
+
+    #[test]
+    fn validating_logs() {
+        let lines = vec![
+            "[1518-03-26 23:50] falls asleep",
+            "[1518-03-26 23:58] Guard #10 begins shift",
+            "[1518-03-27 00:05] wakes up",
+            "[1518-03-27 00:10] falls asleep",
+            "[1518-03-27 00:20] falls asleep",
+            "[1518-03-27 00:30] wakes up",
+            "[1518-03-27 00:40] falls asleep",
+            "[1518-03-27 23:45] Guard #20 begins shift",
+            "[1518-03-27 23:50] falls asleep",
+            "[1518-03-28 01:30] wakes up",
+            "[1518-03-29 00:10] falls asleep",
+        ];
+        let logs = parse_logs(lines).unwrap();
+
+        let problems: Vec<(Problem, Option<u32>, String)> =
+            validate(logs.clone(), Window::default())
+                .into_iter()
+                .map(|d| (d.problem, d.guard, d.time.format("%d %H:%M").to_string()))
+                .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (Problem::NoShift, None, String::from("26 23:50")),
+                (Problem::OrphanWake, Some(10), String::from("27 00:05")),
+                (Problem::DoubleSleep, Some(10), String::from("27 00:20")),
+                (Problem::UnclosedSleep, Some(10), String::from("27 00:40")),
+                (
+                    Problem::OutsideWindow(Window::default()),
+                    Some(20),
+                    String::from("27 23:50")
+                ),
+                (
+                    Problem::OutsideWindow(Window::default()),
+                    Some(20),
+                    String::from("28 01:30")
+                ),
+                (Problem::UnclosedSleep, Some(20), String::from("29 00:10")),
+            ]
+        );
+        assert_eq!(
+            validate(logs.clone(), Window::default())[1].to_string(),
+            "[1518-03-27 00:05] guard #10 woke up without having fallen asleep"
+        );
+
+        assert_eq!(
+            check_logs(logs.clone(), Mode::Strict, Window::default())
+                .unwrap_err()
+                .len(),
+            7
+        );
+
+        let (repaired, diagnostics) = check_logs(logs, Mode::Lenient, Window::default()).unwrap();
+        assert_eq!(diagnostics.len(), 7);
+        assert!(validate(repaired.clone(), Window::default()).is_empty());
+
+        let guards = logs_to_guards(repaired);
+        assert_eq!(guards[&10].sum_minutes_asleep(), 20 + 20);
+        assert_eq!(guards[&20].sum_minutes_asleep(), 60 + 50);
+    }
+
+    #[test]
+    fn strictly_checking_valid_logs() {
+        let logs = vec![
+            log("[1518-03-27 00:03] Guard #2251 begins shift"),
+            log("[1518-03-27 00:11] falls asleep"),
+            log("[1518-03-27 00:57] wakes up"),
+        ];
+        let (checked, diagnostics) = check_logs(logs, Mode::Strict, Window::default()).unwrap();
+        assert_eq!(checked.len(), 3);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn dropping_naps_outside_the_window() {
+        let logs = vec![
+            log("[1518-03-27 00:03] Guard #2251 begins shift"),
+            log("[1518-03-27 01:10] falls asleep"),
+            log("[1518-03-27 01:20] wakes up"),
+            log("[1518-03-27 23:40] Guard #2251 begins shift"),
+            log("[1518-03-27 23:50] falls asleep"),
+            log("[1518-03-27 23:55] wakes up"),
+        ];
+        let (repaired, diagnostics) = check_logs(logs, Mode::Lenient, Window::default()).unwrap();
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            repaired.iter().map(|log| &log.log_type).collect::<Vec<_>>(),
+            vec![&LogType::ShiftStart(2251), &LogType::ShiftStart(2251)]
+        );
+        assert_eq!(logs_to_guards(repaired)[&2251].naps.len(), 0);
+    }
+}
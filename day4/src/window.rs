@@ -0,0 +1,130 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use std::fmt;
+use std::str::FromStr;
+
+/// The midnight of the night `time` belongs to, which is the closest one:
+/// guards starting their shift before midnight sleep after it.
+pub fn midnight(time: DateTime<Utc>) -> DateTime<Utc> {
+    let date = (time + Duration::hours(12)).date_naive();
+    Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// The part of each night that guards are watched in, in minutes relative
+/// to midnight, and how many minutes each step of a sleep histogram covers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Window {
+    pub start: i64,
+    /// The first minute after the window.
+    pub end: i64,
+    pub resolution: i64,
+}
+
+impl Window {
+    pub fn new(start: i64, end: i64) -> Window {
+        assert!(start < end, "a window has to end after it starts");
+        Window {
+            start,
+            end,
+            resolution: 1,
+        }
+    }
+
+    pub fn with_resolution(self, resolution: i64) -> Window {
+        assert!(resolution > 0, "a resolution has to be at least a minute");
+        Window { resolution, ..self }
+    }
+
+    /// The number of steps in a histogram, the last one possibly shorter.
+    pub fn steps(&self) -> usize {
+        ((self.end - self.start + self.resolution - 1) / self.resolution) as usize
+    }
+
+    /// The step that `minute` after midnight falls into, if any.
+    pub fn step(&self, minute: i64) -> Option<usize> {
+        if minute < self.start || minute >= self.end {
+            return None;
+        }
+        Some(((minute - self.start) / self.resolution) as usize)
+    }
+
+    /// The first minute after midnight of `step`.
+    pub fn minute(&self, step: usize) -> i64 {
+        self.start + step as i64 * self.resolution
+    }
+
+    /// The window in the night of `time`, as times.
+    pub fn around(&self, time: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let midnight = midnight(time);
+        (
+            midnight + Duration::minutes(self.start),
+            midnight + Duration::minutes(self.end),
+        )
+    }
+}
+
+/// The midnight hour, minute by minute.
+impl Default for Window {
+    fn default() -> Window {
+        Window::new(0, 60)
+    }
+}
+
+/// `minute` relative to midnight as a time of day, like `23:45`.
+pub(crate) fn clock(minute: i64) -> String {
+    let minute = minute.rem_euclid(24 * 60);
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", clock(self.start), clock(self.end))
+    }
+}
+
+/// Parses windows like `23:30-01:00`. Times from noon on are before
+/// midnight.
+impl FromStr for Window {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Window, String> {
+        let invalid = || format!("invalid window `{}`, expected one like `23:30-01:00`", s);
+
+        let minute = |time: &str| -> Option<i64> {
+            let (hour, minute) = time.split_once(':')?;
+            let (hour, minute) = (hour.parse::<i64>().ok()?, minute.parse::<i64>().ok()?);
+            if !(0..24).contains(&hour) || !(0..60).contains(&minute) {
+                return None;
+            }
+            let minute = hour * 60 + minute;
+            Some(if hour >= 12 { minute - 24 * 60 } else { minute })
+        };
+
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        match (minute(start), minute(end)) {
+            (Some(start), Some(end)) if start < end => Ok(Window::new(start, end)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows() {
+        let window: Window = "23:30-01:00".parse().unwrap();
+        assert_eq!(window, Window::new(-30, 60));
+        assert_eq!(window.to_string(), "23:30-01:00");
+        assert!("01:00-00:30".parse::<Window>().is_err());
+        assert!("0:00-1:61".parse::<Window>().is_err());
+
+        let window = window.with_resolution(20);
+        assert_eq!(window.steps(), 5);
+        assert_eq!(window.step(-30), Some(0));
+        assert_eq!(window.step(59), Some(4));
+        assert_eq!(window.step(60), None);
+        assert_eq!(window.minute(2), 10);
+    }
+}
@@ -0,0 +1,89 @@
+use common::parse::ParseError;
+use common::{Answer, Solution};
+
+pub fn units_can_react(a: char, b: char) -> bool {
+    a != b && a.to_lowercase().to_string() == b.to_lowercase().to_string()
+}
+
+pub fn react_all(units: &mut Vec<char>) {
+    let mut len = units.len();
+    let mut i = 1;
+
+    while i < len {
+        if units_can_react(units[i - 1], units[i]) {
+            units.remove(i - 1);
+            units.remove(i - 1);
+            if i > 1 {
+                i -= 1;
+            }
+            len -= 2;
+            continue;
+        }
+        i += 1;
+    }
+}
+
+pub fn shortest_polymer(units: &[char]) -> usize {
+    let mut shortest = units.len();
+    for c in b'a'..=b'z' {
+        let unit1 = c as char;
+        let unit2 = unit1.to_uppercase().next().unwrap();
+
+        let mut filtered = units
+            .iter()
+            .filter(|&&u| u != unit1 && u != unit2)
+            .cloned()
+            .collect();
+
+        react_all(&mut filtered);
+
+        let len = filtered.len();
+        if len < shortest {
+            shortest = len;
+        }
+    }
+    shortest
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<char>;
+
+    fn parse(input: &str) -> Result<Vec<char>, ParseError> {
+        let mut units: Vec<char> = input.trim_end().chars().collect();
+        react_all(&mut units);
+        Ok(units)
+    }
+
+    fn part_1(units: &Vec<char>) -> Answer {
+        Answer::new(units.len())
+    }
+
+    fn part_2(units: &Vec<char>) -> Answer {
+        Answer::new(shortest_polymer(units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_all_reacting_units() {
+        let mut input: Vec<char> = "dabAcCaCBAcCcaDA".chars().collect();
+        let expected: Vec<char> = "dabCBAcaDA".chars().collect();
+
+        react_all(&mut input);
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn test_units_can_react() {
+        assert!(units_can_react('A', 'a'));
+        assert!(units_can_react('b', 'B'));
+        assert!(!units_can_react('b', 'b'));
+        assert!(!units_can_react('a', 'B'));
+        assert!(!units_can_react('b', 'A'));
+    }
+}
@@ -0,0 +1,197 @@
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use grid::{Bounds, Point};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+pub struct Grid {
+    coords: Vec<Point>,
+    bounds: Bounds,
+}
+
+impl Grid {
+    pub fn new(coords: Vec<Point>) -> Grid {
+        // The grid always starts at 0,0, no matter where the first coordinate is.
+        let max = Bounds::of(coords.iter().cloned())
+            .map(|bounds| bounds.max)
+            .unwrap_or_default();
+
+        Grid {
+            coords,
+            bounds: Bounds::new(Point::new(0, 0), max),
+        }
+    }
+
+    pub fn find_closest(&self, p: Point) -> Option<usize> {
+        let mut tied = true;
+        let mut closest_idx: usize = 0;
+        let mut shortest_distance: i32 = -1;
+
+        for (i, c) in self.coords.iter().enumerate() {
+            let dist = c.manhattan_distance(p);
+
+            if dist == shortest_distance && shortest_distance != -1 {
+                tied = true;
+            }
+
+            if dist < shortest_distance || shortest_distance == -1 {
+                tied = false;
+                shortest_distance = dist;
+                closest_idx = i;
+            }
+        }
+
+        if tied {
+            None
+        } else {
+            Some(closest_idx)
+        }
+    }
+
+    pub fn biggest_area(&self) -> (usize, i32) {
+        let mut areas: HashMap<usize, i32> = HashMap::new();
+        let mut infinite_areas: HashSet<usize> = HashSet::new();
+
+        for p in self.bounds.points() {
+            if let Some(closest) = self.find_closest(p) {
+                let count = areas.entry(closest).or_insert(0);
+                *count += 1;
+                if self.bounds.is_edge(p) {
+                    infinite_areas.insert(closest);
+                }
+            }
+        }
+
+        let mut max_index = 0;
+        let mut max_area = 0;
+
+        for (&coord_idx, &coord_area) in areas.iter() {
+            if coord_area > max_area && !infinite_areas.contains(&coord_idx) {
+                max_index = coord_idx;
+                max_area = coord_area;
+            }
+        }
+
+        (max_index, max_area)
+    }
+
+    pub fn biggest_region(&self) -> i32 {
+        let max_dist = 10000;
+        let mut region_size = 0;
+
+        for p in self.bounds.points() {
+            let mut local_dist = 0;
+            for c in self.coords.iter() {
+                local_dist += c.manhattan_distance(p)
+            }
+            if local_dist < max_dist {
+                region_size += 1;
+            }
+        }
+
+        region_size
+    }
+}
+
+fn parse_coord(line: &str) -> Result<Point, ParseError> {
+    let mut splitted = line.splitn(2, ", ");
+    let x_field = splitted.next().unwrap();
+    let y_field = splitted
+        .next()
+        .ok_or_else(|| ParseError::mismatch(line, "a coordinate like `1, 6`"))?;
+
+    let x = parse_field::<i32>(line, 0, x_field, "an x coordinate")?;
+    let y = parse_field::<i32>(line, x_field.len() + 2, y_field, "a y coordinate")?;
+    Ok(Point::new(x, y))
+}
+
+pub fn parse_coords(input: &str) -> Result<Vec<Point>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_coord(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Grid, ParseError> {
+        Ok(Grid::new(parse_coords(input)?))
+    }
+
+    fn part_1(grid: &Grid) -> Answer {
+        let (coordinate, area_sum) = grid.biggest_area();
+        Answer::new(area_sum).with("coordinate", coordinate)
+    }
+
+    fn part_2(grid: &Grid) -> Answer {
+        Answer::new(grid.biggest_region())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_coords() -> Vec<Point> {
+        // ..........
+        // .A........
+        // ..........
+        // ........C.
+        // ...D......
+        // .....E....
+        // .B........
+        // ..........
+        // ..........
+        // ........F.
+        vec![
+            Point::new(1, 1), // A
+            Point::new(1, 6), // B
+            Point::new(8, 3), // C
+            Point::new(3, 4), // D
+            Point::new(5, 5), // E
+            Point::new(8, 9), // F
+        ]
+    }
+
+    #[test]
+    fn test_finding_closest() {
+        let coords = get_test_coords();
+        let grid = Grid::new(coords);
+        assert_eq!(grid.find_closest(Point::new(2, 2)), Some(0)); // closest to A
+        assert_eq!(grid.find_closest(Point::new(3, 3)), Some(3)); // closest to D
+        assert_eq!(grid.find_closest(Point::new(0, 4)), None); // equally close to A and D
+        assert_eq!(grid.find_closest(Point::new(1, 4)), None); // equally close to A and D
+        assert_eq!(grid.find_closest(Point::new(0, 5)), Some(1)); // closest to B
+        assert_eq!(grid.find_closest(Point::new(6, 0)), Some(2)); // closest to C
+        assert_eq!(grid.find_closest(Point::new(5, 2)), Some(4)); // closest to E
+        assert_eq!(grid.find_closest(Point::new(6, 8)), Some(5)); // closest to F
+    }
+
+    #[test]
+    fn finding_biggest_area() {
+        let coords = get_test_coords();
+        let grid = Grid::new(coords);
+
+        let (idx, area_sum) = grid.biggest_area();
+        assert_eq!(idx, 4);
+        assert_eq!(area_sum, 17);
+    }
+
+    #[test]
+    fn parsing_coords() {
+        let coords = parse_coords("1, 1\n1, 6").unwrap();
+        assert_eq!(coords[1], Point::new(1, 6));
+
+        let error = parse_coords("1, 1\n1 6").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 1);
+
+        let error = parse_coords("1, 1\n1, six").unwrap_err();
+        assert_eq!(error.column, 4);
+        assert_eq!(error.found, "six");
+    }
+}
@@ -0,0 +1,193 @@
+extern crate regex;
+
+use common::parse::ParseError;
+use common::{Answer, Solution};
+use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+pub fn build_graph<'a, I>(lines: I) -> Result<HashMap<&'a str, Vec<&'a str>>, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let line_re: Regex =
+        Regex::new(r"Step\s([A-Z])\smust\sbe\sfinished\sbefore\sstep\s([A-Z])").unwrap();
+    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for (i, line) in lines.enumerate() {
+        let caps = line_re.captures(line).ok_or_else(|| {
+            ParseError::mismatch(
+                line,
+                "`Step <A-Z> must be finished before step <A-Z> can begin.`",
+            )
+            .on_line(i + 1)
+        })?;
+        let parent = caps.get(1).unwrap().as_str();
+        let child = caps.get(2).unwrap().as_str();
+        graph.entry(parent).or_default();
+        graph.entry(child).or_default().push(parent);
+    }
+
+    Ok(graph)
+}
+
+fn get_next_steps<'a>(
+    steps: &HashSet<&'a str>,
+    graph: &HashMap<&'a str, Vec<&'a str>>,
+    satisfied: &HashSet<&'a str>,
+) -> Vec<&'a str> {
+    let mut result: Vec<&str> = steps
+        .iter()
+        .cloned()
+        .filter(|step| match graph.get(*step) {
+            Some(deps) => deps.iter().all(|dep| satisfied.contains(dep)),
+            None => true,
+        })
+        .collect();
+    result.sort();
+    result
+}
+
+pub fn build_order(graph: HashMap<&str, Vec<&str>>) -> String {
+    let mut steps: HashSet<&str> = graph.keys().cloned().collect();
+    let mut result: Vec<&str> = Vec::new();
+    let mut satisfied: HashSet<&str> = HashSet::new();
+
+    while !steps.is_empty() {
+        let next = get_next_steps(&steps, &graph, &satisfied);
+        if let Some(&step) = next.first() {
+            satisfied.insert(step);
+            result.push(step);
+            steps.remove(step);
+        }
+    }
+
+    result.into_iter().collect::<String>()
+}
+
+#[derive(Debug)]
+struct Worker<'a> {
+    work_left: u32,
+    step: Option<&'a str>,
+}
+
+fn step_duration(step: &str) -> u32 {
+    step.chars().next().unwrap() as u32 - ('A' as u32) + 1
+}
+
+pub fn work_off_steps(
+    graph: HashMap<&str, Vec<&str>>,
+    num_workers: i32,
+    base_duration: u32,
+) -> i32 {
+    let mut steps: HashSet<&str> = graph.keys().cloned().collect();
+    let steps_len = steps.len();
+    let mut worked_off: HashSet<&str> = HashSet::new();
+
+    let mut workers = Vec::new();
+    for _ in 0..num_workers {
+        workers.push(Worker {
+            work_left: 0,
+            step: None,
+        });
+    }
+
+    let mut duration = 0;
+
+    while !steps.is_empty() || worked_off.len() != steps_len {
+        let next = get_next_steps(&steps, &graph, &worked_off);
+
+        for (worker, step) in workers.iter_mut().filter(|w| w.step.is_none()).zip(next) {
+            worker.work_left = base_duration + step_duration(step);
+            worker.step = Some(step);
+
+            steps.remove(step);
+        }
+
+        for w in workers.iter_mut().filter(|w| w.step.is_some()) {
+            w.work_left -= 1;
+            if w.work_left == 0 {
+                worked_off.insert(w.step.unwrap());
+                w.step = None;
+            }
+        }
+
+        duration += 1;
+    }
+
+    duration
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    // The graph borrows its step names from the input, so each part builds
+    // its own graph from the raw lines that `parse` has already checked.
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        build_graph(input.lines())?;
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &String) -> Answer {
+        Answer::new(build_order(build_graph(input.lines()).unwrap()))
+    }
+
+    fn part_2(input: &String) -> Answer {
+        Answer::new(work_off_steps(build_graph(input.lines()).unwrap(), 5, 60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_input<'a>() -> Vec<&'a str> {
+        include_str!("../example.txt").lines().collect()
+    }
+
+    #[test]
+    fn test_building_graph() {
+        let input = test_input();
+        let graph = build_graph(input.into_iter()).unwrap();
+        let mut keys: Vec<&str> = graph.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["A", "B", "C", "D", "E", "F"]);
+
+        assert_eq!(graph[&"C"].len(), 0);
+        assert_eq!(graph[&"A"], vec!["C"]);
+        assert_eq!(graph[&"B"], vec!["A"]);
+        assert_eq!(graph[&"E"], vec!["B", "D", "F"]);
+        assert_eq!(graph[&"D"], vec!["A"]);
+        assert_eq!(graph[&"F"], vec!["C"]);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let input = test_input();
+        let graph = build_graph(input.into_iter()).unwrap();
+        let order = build_order(graph);
+
+        assert_eq!(order, "CABDFE");
+    }
+
+    #[test]
+    fn test_working_off_the_steps() {
+        let input = test_input();
+        let graph = build_graph(input.into_iter()).unwrap();
+        let num_workers = 2;
+        let total_duration = work_off_steps(graph.clone(), num_workers, 0);
+        assert_eq!(total_duration, 15);
+    }
+
+    #[test]
+    fn test_building_graph_from_bad_lines() {
+        let mut input = test_input();
+        input.insert(2, "Step a must be finished before step B can begin.");
+
+        let error = build_graph(input.into_iter()).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 1);
+    }
+}
@@ -0,0 +1,169 @@
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    let mut numbers = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        for field in line.split_whitespace() {
+            // Where the field starts in the line, to point at it.
+            let start = field.as_ptr() as usize - line.as_ptr() as usize;
+            let number =
+                parse_field::<u32>(line, start, field, "a number").map_err(|e| e.on_line(i + 1))?;
+            numbers.push(number);
+        }
+    }
+
+    Ok(numbers)
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub children: Vec<Node>,
+    pub metadata: Vec<u32>,
+}
+
+/// Returns `None` if the input runs out of numbers before the tree is complete.
+pub fn build_graph<'a, I>(input: &mut I) -> Option<Node>
+where
+    I: Iterator<Item = &'a u32>,
+{
+    let num_children = input.next()?;
+    let num_metadata = input.next()?;
+
+    let children = (0..*num_children)
+        .map(|_| build_graph(input))
+        .collect::<Option<Vec<Node>>>()?;
+    let metadata = (0..*num_metadata)
+        .map(|_| input.next().cloned())
+        .collect::<Option<Vec<u32>>>()?;
+
+    Some(Node { children, metadata })
+}
+
+pub fn parse_tree(input: &str) -> Result<Node, ParseError> {
+    let numbers = parse_input(input)?;
+    let mut iter = numbers.iter();
+
+    build_graph(&mut iter).ok_or_else(|| {
+        let last_line = input.lines().last().unwrap_or("");
+        ParseError::new(
+            last_line,
+            last_line.len(),
+            "",
+            "more numbers to complete the tree",
+        )
+        .on_line(input.lines().count().max(1))
+    })
+}
+
+pub fn sum_metadata(n: &Node) -> u32 {
+    n.children
+        .iter()
+        .fold(n.metadata.iter().sum(), |sum, c| sum + sum_metadata(c))
+}
+
+pub fn value_of_node(n: &Node) -> u32 {
+    if n.children.is_empty() {
+        return n.metadata.iter().sum();
+    }
+
+    // A metadata entry of 0 doesn't refer to any child.
+    n.metadata
+        .iter()
+        .filter_map(|m| m.checked_sub(1))
+        .filter_map(|i| n.children.get(i as usize))
+        .map(value_of_node)
+        .sum()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Node;
+
+    fn parse(input: &str) -> Result<Node, ParseError> {
+        parse_tree(input)
+    }
+
+    fn part_1(root: &Node) -> Answer {
+        Answer::new(sum_metadata(root))
+    }
+
+    fn part_2(root: &Node) -> Answer {
+        Answer::new(value_of_node(root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_input<'a>() -> &'a str {
+        include_str!("../example.txt").trim_end()
+    }
+
+    #[test]
+    fn test_parsing_input() {
+        let input = test_input();
+        assert_eq!(
+            parse_input(input).unwrap(),
+            vec![2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_building_graph() {
+        let input = parse_input(test_input()).unwrap();
+
+        let mut iter = input.iter();
+        let root = build_graph(&mut iter).unwrap();
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.metadata, vec![1, 1, 2]);
+
+        assert_eq!(root.children[0].children.len(), 0);
+        assert_eq!(root.children[0].metadata, vec![10, 11, 12]);
+
+        assert_eq!(root.children[1].children.len(), 1);
+        assert_eq!(root.children[1].metadata, vec![2]);
+
+        assert_eq!(root.children[1].children[0].children.len(), 0);
+        assert_eq!(root.children[1].children[0].metadata, vec![99]);
+    }
+
+    #[test]
+    fn test_summing_up_metadata() {
+        let input = parse_input(test_input()).unwrap();
+        let mut iter = input.iter();
+        let root = build_graph(&mut iter).unwrap();
+
+        let sum = sum_metadata(&root);
+        assert_eq!(sum, 138);
+    }
+
+    #[test]
+    fn calculating_value_of_node() {
+        let input = parse_input(test_input()).unwrap();
+        let mut iter = input.iter();
+        let root = build_graph(&mut iter).unwrap();
+
+        let value = value_of_node(&root);
+        assert_eq!(value, 66);
+    }
+
+    #[test]
+    fn parsing_bad_input() {
+        let error = parse_input("2 3 0 3 1o 11").unwrap_err();
+        assert_eq!(error.column, 9);
+        assert_eq!(error.found, "1o");
+
+        let error = parse_input("2 3\t0  3 1o 11").unwrap_err();
+        assert_eq!(error.column, 10);
+        assert_eq!(error.found, "1o");
+
+        let error = parse_tree("2 3 0 3 10 11 12\n1 1 0 1 99 2").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 13);
+        assert_eq!(error.found, "");
+    }
+}
@@ -0,0 +1,147 @@
+extern crate regex;
+
+use common::parse::{parse_field, ParseError};
+use common::{Answer, Solution};
+use regex::Regex;
+
+pub fn parse_input(input: &str) -> Result<(u32, u32), ParseError> {
+    let line_re: Regex = Regex::new(r"(\d+).*\s(\d+)").unwrap();
+
+    let line = input.lines().next().unwrap_or("");
+    let caps = line_re.captures(line).ok_or_else(|| {
+        ParseError::mismatch(line, "`<n> players; last marble is worth <n> points`")
+    })?;
+
+    let players = caps.get(1).unwrap();
+    let last = caps.get(2).unwrap();
+    let num_players = parse_field::<u32>(
+        line,
+        players.start(),
+        players.as_str(),
+        "a number of players",
+    )?;
+    if num_players == 0 {
+        return Err(ParseError::new(
+            line,
+            players.start(),
+            players.as_str(),
+            "at least one player",
+        ));
+    }
+
+    let last_marble = parse_field::<u32>(line, last.start(), last.as_str(), "a marble worth")?;
+
+    Ok((num_players, last_marble))
+}
+
+#[derive(Debug)]
+struct Marble {
+    id: usize,
+    value: u32,
+    next: usize,
+    prev: usize,
+}
+
+pub fn calculate_high_score(num_players: u32, last_marble: u32) -> u32 {
+    let mut players: Vec<u32> = (0..num_players).map(|_| 0).collect();
+    let mut current_player = 0;
+
+    let mut marbles = vec![Marble {
+        id: 0,
+        value: 0,
+        next: 0,
+        prev: 0,
+    }];
+    let mut current = 0;
+
+    for marble in 1..=last_marble {
+        if marble % 23 == 0 {
+            let mut i = 7;
+            while i > 0 {
+                current = marbles[current].prev;
+                i -= 1;
+            }
+            players[current_player] += marble + marbles[current].value;
+
+            let prev_id = marbles[current].prev;
+            let next_id = marbles[current].next;
+            marbles[prev_id].next = next_id;
+            marbles[next_id].prev = prev_id;
+            current = next_id;
+        } else {
+            let left_id = marbles[current].next;
+            let right_id = marbles[left_id].next;
+
+            let new = Marble {
+                id: marbles.len(),
+                value: marble,
+                prev: left_id,
+                next: right_id,
+            };
+            marbles[left_id].next = new.id;
+            marbles[right_id].prev = new.id;
+            current = new.id;
+            marbles.push(new);
+        }
+
+        current_player = (current_player + 1) % num_players as usize;
+    }
+
+    *players.iter().max().unwrap()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = (u32, u32);
+
+    fn parse(input: &str) -> Result<(u32, u32), ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(&(players, last_marble_worth): &(u32, u32)) -> Answer {
+        Answer::new(calculate_high_score(players, last_marble_worth))
+    }
+
+    fn part_2(&(players, last_marble_worth): &(u32, u32)) -> Answer {
+        Answer::new(calculate_high_score(players, last_marble_worth * 100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_input<'a>() -> &'a str {
+        include_str!("../example.txt").trim_end()
+    }
+
+    #[test]
+    fn test_parsing_input() {
+        let input = test_input();
+        assert_eq!(parse_input(input), Ok((10, 1618)));
+    }
+
+    #[test]
+    fn test_parsing_bad_input() {
+        let error = parse_input("10 players; last marble is worth lots of points").unwrap_err();
+        assert_eq!(error.column, 1);
+
+        let error = parse_input("0 players; last marble is worth 1618 points").unwrap_err();
+        assert_eq!(error.to_string(), "expected at least one player, found `0`");
+
+        let error = parse_input("10 players; last marble is worth 99999999999 points").unwrap_err();
+        assert_eq!(error.column, 34);
+        assert_eq!(error.found, "99999999999");
+    }
+
+    #[test]
+    fn test_calculating_high_score() {
+        assert_eq!(calculate_high_score(9, 25), 32);
+        assert_eq!(calculate_high_score(10, 1618), 8317);
+        assert_eq!(calculate_high_score(13, 7999), 146373);
+        assert_eq!(calculate_high_score(17, 1104), 2764);
+        assert_eq!(calculate_high_score(21, 6111), 54718);
+        assert_eq!(calculate_high_score(30, 5807), 37305);
+    }
+}
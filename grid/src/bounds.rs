@@ -0,0 +1,93 @@
+use crate::point::Point;
+
+/// The smallest rectangle containing a set of points. Both corners are part
+/// of it, so a single point has a width and height of 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(min: Point, max: Point) -> Bounds {
+        Bounds { min, max }
+    }
+
+    /// Returns `None` if there are no points.
+    pub fn of<I: IntoIterator<Item = Point>>(points: I) -> Option<Bounds> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Bounds::new(first, first), |bounds, p| {
+            Bounds::new(
+                Point::new(bounds.min.x.min(p.x), bounds.min.y.min(p.y)),
+                Point::new(bounds.max.x.max(p.x), bounds.max.y.max(p.y)),
+            )
+        }))
+    }
+
+    pub fn width(&self) -> i32 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /// Whether `p` lies on the outermost rows or columns.
+    pub fn is_edge(&self, p: Point) -> bool {
+        self.contains(p)
+            && (p.x == self.min.x || p.x == self.max.x || p.y == self.min.y || p.y == self.max.y)
+    }
+
+    /// Every point inside, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finding_bounds() {
+        let bounds = Bounds::of(vec![Point::new(3, -2), Point::new(-1, 4), Point::new(0, 0)]);
+        assert_eq!(
+            bounds,
+            Some(Bounds::new(Point::new(-1, -2), Point::new(3, 4)))
+        );
+
+        let bounds = bounds.unwrap();
+        assert_eq!(bounds.width(), 5);
+        assert_eq!(bounds.height(), 7);
+        assert!(bounds.contains(Point::new(3, 4)));
+        assert!(!bounds.contains(Point::new(4, 4)));
+        assert!(bounds.is_edge(Point::new(-1, 0)));
+        assert!(!bounds.is_edge(Point::new(0, 0)));
+
+        assert_eq!(Bounds::of(Vec::new()), None);
+    }
+
+    #[test]
+    fn iterating_in_reading_order() {
+        let bounds = Bounds::new(Point::new(1, 1), Point::new(2, 2));
+        assert_eq!(
+            bounds.points().collect::<Vec<Point>>(),
+            vec![
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(2, 2)
+            ]
+        );
+
+        let empty = Bounds::new(Point::new(1, 1), Point::new(0, 0));
+        assert_eq!(empty.points().count(), 0);
+    }
+}
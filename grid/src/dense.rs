@@ -0,0 +1,231 @@
+use crate::bounds::Bounds;
+use crate::point::Point;
+use crate::Grid;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A grid with a cell for every point of a rectangle, stored row by row.
+/// The rectangle doesn't have to start at 0,0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseGrid<T> {
+    origin: Point,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> DenseGrid<T> {
+    /// A grid from 0,0 to `width - 1`,`height - 1` with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> DenseGrid<T> {
+        DenseGrid {
+            origin: Point::new(0, 0),
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid with a cell for every point in `bounds`.
+    pub fn covering(bounds: Bounds, fill: T) -> DenseGrid<T> {
+        let width = bounds.width().max(0) as usize;
+        let height = bounds.height().max(0) as usize;
+        DenseGrid {
+            origin: bounds.min,
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Grows the grid so that it covers `bounds` as well, filling the new
+    /// cells with `fill`.
+    pub fn expand(&mut self, bounds: Bounds, fill: T) {
+        let current = match self.bounds() {
+            Some(current) if current.contains(bounds.min) && current.contains(bounds.max) => {
+                return;
+            }
+            Some(current) => current,
+            None => {
+                *self = DenseGrid::covering(bounds, fill);
+                return;
+            }
+        };
+
+        let union = Bounds::of(vec![current.min, current.max, bounds.min, bounds.max]).unwrap();
+        let mut grown = DenseGrid::covering(union, fill);
+        for (y, row) in self.rows().enumerate() {
+            let start = grown
+                .offset(Point::new(self.origin.x, self.origin.y + y as i32))
+                .unwrap();
+            grown.cells[start..start + row.len()].clone_from_slice(row);
+        }
+        *self = grown;
+    }
+}
+
+impl<T> DenseGrid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `None` if the grid has no cells at all.
+    pub fn bounds(&self) -> Option<Bounds> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        let max = Point::new(
+            self.origin.x + self.width as i32 - 1,
+            self.origin.y + self.height as i32 - 1,
+        );
+        Some(Bounds::new(self.origin, max))
+    }
+
+    #[inline]
+    fn offset(&self, p: Point) -> Option<usize> {
+        // Points left of or above the origin wrap around to huge numbers,
+        // which saves checking for them separately.
+        let x = p.x.wrapping_sub(self.origin.x) as u32 as usize;
+        let y = p.y.wrapping_sub(self.origin.y) as u32 as usize;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(y * self.width + x)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(move |i| &mut self.cells[i])
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (origin, width) = (self.origin, self.width);
+        (0..self.cells.len())
+            .map(move |i| Point::new(origin.x + (i % width) as i32, origin.y + (i / width) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The cells of each row, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` doesn't allow a size of 0, but then there are no cells
+        // and thus no rows either.
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl<T> Index<Point> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.offset(p) {
+            Some(i) => &self.cells[i],
+            None => panic!("{} is outside of the grid {:?}", p, self.bounds()),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for DenseGrid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        match self.offset(p) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{} is outside of the grid {:?}", p, self.bounds()),
+        }
+    }
+}
+
+impl<T> Grid for DenseGrid<T> {
+    type Cell = T;
+
+    fn get(&self, p: Point) -> Option<&T> {
+        DenseGrid::get(self, p)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        DenseGrid::bounds(self)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DenseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexing_by_point() {
+        let mut grid = DenseGrid::new(3, 2, '.');
+        grid[Point::new(2, 1)] = '#';
+
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'#'));
+        assert_eq!(grid.get(Point::new(3, 1)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "...\n..#\n");
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds::new(Point::new(0, 0), Point::new(2, 1)))
+        );
+    }
+
+    #[test]
+    fn covering_bounds_away_from_the_origin() {
+        let bounds = Bounds::new(Point::new(1, 1), Point::new(300, 300));
+        let mut grid = DenseGrid::covering(bounds, 0);
+        grid[Point::new(300, 300)] = 5;
+        grid[Point::new(1, 1)] = 1;
+
+        assert_eq!(grid.width(), 300);
+        assert_eq!(grid.bounds(), Some(bounds));
+        assert_eq!(grid.get(Point::new(0, 0)), None);
+        assert_eq!(grid.iter().next(), Some((Point::new(1, 1), &1)));
+        assert_eq!(grid.iter().last(), Some((Point::new(300, 300), &5)));
+    }
+
+    #[test]
+    fn empty_grids() {
+        let grid = DenseGrid::new(0, 0, ' ');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn expanding() {
+        let mut grid = DenseGrid::new(2, 2, '.');
+        grid[Point::new(1, 1)] = '#';
+
+        grid.expand(Bounds::new(Point::new(-1, 0), Point::new(0, 2)), ' ');
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds::new(Point::new(-1, 0), Point::new(1, 2)))
+        );
+        assert_eq!(grid.to_string(), " ..\n .#\n   \n");
+
+        let mut empty = DenseGrid::new(0, 0, 0);
+        empty.expand(Bounds::new(Point::new(3, 3), Point::new(4, 3)), 1);
+        assert_eq!(empty.values().sum::<i32>(), 2);
+    }
+}
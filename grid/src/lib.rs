@@ -0,0 +1,118 @@
+mod bounds;
+mod dense;
+mod point;
+mod sparse;
+
+pub use crate::bounds::Bounds;
+pub use crate::dense::DenseGrid;
+pub use crate::point::{Neighbours, Point};
+pub use crate::sparse::SparseGrid;
+
+use std::collections::{HashSet, VecDeque};
+
+/// What both grid backends have in common. Everything that only needs to
+/// look at cells, like flood filling a region, works on either of them.
+pub trait Grid {
+    type Cell;
+
+    fn get(&self, p: Point) -> Option<&Self::Cell>;
+    fn bounds(&self) -> Option<Bounds>;
+
+    /// The neighbours of `p` that have a cell.
+    fn neighbours(&self, p: Point, neighbours: Neighbours) -> Vec<Point> {
+        p.neighbours(neighbours)
+            .filter(|n| self.get(*n).is_some())
+            .collect()
+    }
+
+    /// All points connected to `start` through cells for which `belongs`
+    /// returns true. Empty if `start` itself doesn't belong to the region.
+    fn region<F>(&self, start: Point, neighbours: Neighbours, mut belongs: F) -> HashSet<Point>
+    where
+        F: FnMut(Point, &Self::Cell) -> bool,
+    {
+        let mut region = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        match self.get(start) {
+            Some(cell) if belongs(start, cell) => {
+                region.insert(start);
+                queue.push_back(start);
+            }
+            _ => return region,
+        }
+
+        while let Some(p) = queue.pop_front() {
+            for n in p.neighbours(neighbours) {
+                if region.contains(&n) {
+                    continue;
+                }
+                if let Some(cell) = self.get(n) {
+                    if belongs(n, cell) {
+                        region.insert(n);
+                        queue.push_back(n);
+                    }
+                }
+            }
+        }
+
+        region
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(drawing: &str) -> DenseGrid<char> {
+        let lines: Vec<&str> = drawing.lines().collect();
+        let mut grid = DenseGrid::new(lines[0].len(), lines.len(), '.');
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid[Point::new(x as i32, y as i32)] = c;
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn filling_regions() {
+        let grid = parse("##..\n#..#\n.###\n#..#");
+        let is_wall = |_, c: &char| *c == '#';
+
+        let region = grid.region(Point::new(0, 0), Neighbours::Four, is_wall);
+        assert_eq!(region.len(), 3);
+        let region = grid.region(Point::new(3, 1), Neighbours::Four, is_wall);
+        assert_eq!(region.len(), 5);
+
+        // Only diagonally, the wall in the bottom left corner touches the
+        // others, which in turn touch the top left ones.
+        let region = grid.region(Point::new(0, 3), Neighbours::Four, is_wall);
+        assert_eq!(region.len(), 1);
+        let region = grid.region(Point::new(0, 3), Neighbours::Eight, is_wall);
+        assert_eq!(region.len(), 9);
+
+        let region = grid.region(Point::new(2, 0), Neighbours::Four, is_wall);
+        assert!(region.is_empty());
+        let region = grid.region(Point::new(9, 9), Neighbours::Four, |_, _| true);
+        assert!(region.is_empty());
+    }
+
+    #[test]
+    fn filling_sparse_regions() {
+        let mut grid = SparseGrid::new();
+        for &(x, y) in &[(0, 0), (1, 0), (1, 1), (5, 5), (6, 6)] {
+            grid.insert(Point::new(x, y), ());
+        }
+
+        let region = grid.region(Point::new(0, 0), Neighbours::Four, |_, _| true);
+        assert_eq!(region.len(), 3);
+        let region = grid.region(Point::new(5, 5), Neighbours::Eight, |_, _| true);
+        assert_eq!(region.len(), 2);
+
+        assert_eq!(
+            grid.neighbours(Point::new(1, 0), Neighbours::Four),
+            vec![Point::new(0, 0), Point::new(1, 1)]
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// A position on a grid. `x` is the column and `y` the row, with `y` growing
+/// downwards, which is how every puzzle draws its grids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Which points count as next to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbours {
+    /// Up, left, right and down.
+    Four,
+    /// The four and the diagonals.
+    Eight,
+}
+
+// Both in reading order, so that iterating over neighbours doesn't change
+// which of several equal candidates gets picked first.
+const FOUR: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const EIGHT: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl Neighbours {
+    fn offsets(self) -> &'static [(i32, i32)] {
+        match self {
+            Neighbours::Four => &FOUR,
+            Neighbours::Eight => &EIGHT,
+        }
+    }
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn neighbours(self, neighbours: Neighbours) -> impl Iterator<Item = Point> {
+        neighbours
+            .offsets()
+            .iter()
+            .map(move |&(dx, dy)| Point::new(self.x + dx, self.y + dy))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measuring_distances() {
+        assert_eq!(Point::new(1, 1).manhattan_distance(Point::new(5, 5)), 8);
+        assert_eq!(Point::new(-2, 3).manhattan_distance(Point::new(1, -1)), 7);
+        assert_eq!(Point::new(4, 4).manhattan_distance(Point::new(4, 4)), 0);
+    }
+
+    #[test]
+    fn listing_neighbours() {
+        let point = Point::new(0, 0);
+        assert_eq!(
+            point.neighbours(Neighbours::Four).collect::<Vec<Point>>(),
+            vec![
+                Point::new(0, -1),
+                Point::new(-1, 0),
+                Point::new(1, 0),
+                Point::new(0, 1)
+            ]
+        );
+
+        let diagonals = point.neighbours(Neighbours::Eight);
+        assert_eq!(diagonals.count(), 8);
+        assert!(point
+            .neighbours(Neighbours::Eight)
+            .all(|n| n != point && (n.x - point.x).abs() <= 1 && (n.y - point.y).abs() <= 1));
+    }
+}
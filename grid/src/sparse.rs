@@ -0,0 +1,136 @@
+use crate::bounds::Bounds;
+use crate::point::Point;
+use crate::Grid;
+use std::collections::hash_map::{self, HashMap};
+use std::fmt;
+
+/// A grid that only stores the points that have a value, for points spread
+/// far apart or over a region whose size isn't known upfront.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn entry(&mut self, p: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(p)
+    }
+
+    /// The points with a value, in no particular order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().cloned()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, value)| (*p, value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// The bounds of the points with a value, `None` if there are none.
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::of(self.points())
+    }
+}
+
+impl<T> Grid for SparseGrid<T> {
+    type Cell = T;
+
+    fn get(&self, p: Point) -> Option<&T> {
+        SparseGrid::get(self, p)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        SparseGrid::bounds(self)
+    }
+}
+
+/// Draws the bounds of the grid, with a `.` for every point without a value.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+
+        for y in bounds.min.y..=bounds.max.y {
+            for x in bounds.min.x..=bounds.max.x {
+                match self.get(Point::new(x, y)) {
+                    Some(value) => write!(f, "{}", value)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storing_points_far_apart() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Point::new(-50_000, 3), 1);
+        grid.insert(Point::new(50_000, -3), 2);
+        *grid.entry(Point::new(50_000, -3)).or_default() += 1;
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(Point::new(50_000, -3)), Some(&3));
+        assert_eq!(grid.get(Point::new(0, 0)), None);
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds::new(Point::new(-50_000, -3), Point::new(50_000, 3)))
+        );
+    }
+
+    #[test]
+    fn drawing_missing_points() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.to_string(), "");
+
+        grid.insert(Point::new(-1, -1), '#');
+        grid.insert(Point::new(1, 0), '#');
+        assert_eq!(grid.to_string(), "#..\n..#\n");
+    }
+}